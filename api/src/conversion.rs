@@ -1,3 +1,261 @@
+// AnalysisSection: API -> BLL
+impl From<crate::models::AnalysisSection> for devpulse_core::models::AnalysisSection {
+    fn from(section: crate::models::AnalysisSection) -> Self {
+        match section {
+            crate::models::AnalysisSection::Files => devpulse_core::models::AnalysisSection::Files,
+            crate::models::AnalysisSection::Hotspots => {
+                devpulse_core::models::AnalysisSection::Hotspots
+            }
+            crate::models::AnalysisSection::BusFactor => {
+                devpulse_core::models::AnalysisSection::BusFactor
+            }
+            crate::models::AnalysisSection::Ownership => {
+                devpulse_core::models::AnalysisSection::Ownership
+            }
+            crate::models::AnalysisSection::Rework => {
+                devpulse_core::models::AnalysisSection::Rework
+            }
+            crate::models::AnalysisSection::Coupling => {
+                devpulse_core::models::AnalysisSection::Coupling
+            }
+            crate::models::AnalysisSection::Activity => {
+                devpulse_core::models::AnalysisSection::Activity
+            }
+            crate::models::AnalysisSection::WorkPatterns => {
+                devpulse_core::models::AnalysisSection::WorkPatterns
+            }
+            crate::models::AnalysisSection::Languages => {
+                devpulse_core::models::AnalysisSection::Languages
+            }
+            crate::models::AnalysisSection::Compliance => {
+                devpulse_core::models::AnalysisSection::Compliance
+            }
+            crate::models::AnalysisSection::TestRatio => {
+                devpulse_core::models::AnalysisSection::TestRatio
+            }
+            crate::models::AnalysisSection::Outliers => {
+                devpulse_core::models::AnalysisSection::Outliers
+            }
+        }
+    }
+}
+
+// CommitRangeRequest: API -> BLL options
+impl From<&crate::models::CommitRangeRequest> for devpulse_core::models::CommitRangeOptions {
+    fn from(request: &crate::models::CommitRangeRequest) -> Self {
+        let defaults = devpulse_core::models::CommitRangeOptions::default();
+        devpulse_core::models::CommitRangeOptions {
+            sections: request.sections.iter().copied().map(Into::into).collect(),
+            history_mode: request
+                .history_mode
+                .map(Into::into)
+                .unwrap_or(defaults.history_mode),
+            include_paths: request.include_paths.clone(),
+            exclude_paths: request.exclude_paths.clone(),
+            exclude_generated: request
+                .exclude_generated
+                .unwrap_or(defaults.exclude_generated),
+            bus_factor_depth: request
+                .bus_factor_depth
+                .unwrap_or(defaults.bus_factor_depth),
+            rework_window_days: request
+                .rework_window_days
+                .unwrap_or(defaults.rework_window_days),
+            coupling_min_co_changes: request
+                .coupling_min_co_changes
+                .unwrap_or(defaults.coupling_min_co_changes),
+            aliases: request.aliases.clone(),
+            rank_contributors_by: request
+                .rank_contributors_by
+                .map(Into::into)
+                .unwrap_or(defaults.rank_contributors_by),
+            co_author_credit: request
+                .co_author_credit
+                .map(Into::into)
+                .unwrap_or(defaults.co_author_credit),
+            exclude_bots: request.exclude_bots,
+            bot_accounts: request.bot_accounts.clone(),
+            granularity: request
+                .granularity
+                .map(Into::into)
+                .unwrap_or(defaults.granularity),
+            timezone: request.timezone.clone().unwrap_or(defaults.timezone),
+            working_hours: request
+                .working_hours
+                .as_ref()
+                .map(Into::into)
+                .unwrap_or(defaults.working_hours),
+            teams: request.teams.clone(),
+            compliance_rules: request
+                .compliance_rules
+                .as_ref()
+                .map(Into::into)
+                .unwrap_or(defaults.compliance_rules),
+            test_patterns: request.test_patterns.clone(),
+            outlier_threshold: request
+                .outlier_threshold
+                .unwrap_or(defaults.outlier_threshold),
+        }
+    }
+}
+
+// CommitRangeRequest: API -> BLL range
+impl From<&crate::models::CommitRangeRequest> for devpulse_core::models::RangeSpec {
+    fn from(request: &crate::models::CommitRangeRequest) -> Self {
+        devpulse_core::models::RangeSpec {
+            start_commit: request.start_commit.clone(),
+            end_commit: request.end_commit.clone(),
+            revspec: request.revspec.clone(),
+            between_latest_tags: request.between_latest_tags,
+            since: request.since.clone(),
+            until: request.until.clone(),
+            last: request.last,
+            max_commits: request.max_commits,
+        }
+    }
+}
+
+// ChangelogRequest: API -> BLL options
+impl From<&crate::models::ChangelogRequest> for devpulse_core::models::ChangelogOptions {
+    fn from(request: &crate::models::ChangelogRequest) -> Self {
+        let defaults = devpulse_core::models::ChangelogOptions::default();
+        devpulse_core::models::ChangelogOptions {
+            format: request.format.map(Into::into).unwrap_or(defaults.format),
+            template: request.template.clone(),
+            version: request.version.clone(),
+            history_mode: request
+                .history_mode
+                .map(Into::into)
+                .unwrap_or(defaults.history_mode),
+            exclude_bots: request.exclude_bots,
+            bot_accounts: request.bot_accounts.clone(),
+            aliases: request.aliases.clone(),
+        }
+    }
+}
+
+// NextVersionRequest: API -> BLL options
+impl From<&crate::models::NextVersionRequest> for devpulse_core::models::ReleaseOptions {
+    fn from(request: &crate::models::NextVersionRequest) -> Self {
+        let defaults = devpulse_core::models::ReleaseOptions::default();
+        devpulse_core::models::ReleaseOptions {
+            pre_release: request.pre_release.clone(),
+            tag_prefix: request.tag_prefix.clone().unwrap_or(defaults.tag_prefix),
+            history_mode: request
+                .history_mode
+                .map(Into::into)
+                .unwrap_or(defaults.history_mode),
+        }
+    }
+}
+
+// ChangelogFormat: API -> BLL
+impl From<crate::models::ChangelogFormat> for devpulse_core::models::ChangelogFormat {
+    fn from(format: crate::models::ChangelogFormat) -> Self {
+        match format {
+            crate::models::ChangelogFormat::Markdown => {
+                devpulse_core::models::ChangelogFormat::Markdown
+            }
+            crate::models::ChangelogFormat::Json => devpulse_core::models::ChangelogFormat::Json,
+            crate::models::ChangelogFormat::KeepAChangelog => {
+                devpulse_core::models::ChangelogFormat::KeepAChangelog
+            }
+        }
+    }
+}
+
+// HistoryMode: API -> BLL
+impl From<crate::models::HistoryMode> for devpulse_core::models::HistoryMode {
+    fn from(mode: crate::models::HistoryMode) -> Self {
+        match mode {
+            crate::models::HistoryMode::All => devpulse_core::models::HistoryMode::All,
+            crate::models::HistoryMode::FirstParent => {
+                devpulse_core::models::HistoryMode::FirstParent
+            }
+            crate::models::HistoryMode::NoMerges => devpulse_core::models::HistoryMode::NoMerges,
+            crate::models::HistoryMode::MergesOnly => {
+                devpulse_core::models::HistoryMode::MergesOnly
+            }
+        }
+    }
+}
+
+// WorkingHours: API -> BLL
+impl From<&crate::models::WorkingHours> for devpulse_core::models::WorkingHours {
+    fn from(hours: &crate::models::WorkingHours) -> Self {
+        devpulse_core::models::WorkingHours {
+            start_hour: hours.start_hour,
+            end_hour: hours.end_hour,
+            weekends: hours.weekends,
+        }
+    }
+}
+
+// ComplianceRules: API -> BLL
+impl From<&crate::models::ComplianceRules> for devpulse_core::models::ComplianceRules {
+    fn from(rules: &crate::models::ComplianceRules) -> Self {
+        let defaults = devpulse_core::models::ComplianceRules::default();
+        devpulse_core::models::ComplianceRules {
+            allowed_types: rules
+                .allowed_types
+                .clone()
+                .unwrap_or(defaults.allowed_types),
+            require_scope: rules.require_scope,
+            // An explicit 0 turns the limit off.
+            max_subject_length: match rules.max_subject_length {
+                Some(0) => None,
+                Some(max) => Some(max),
+                None => defaults.max_subject_length,
+            },
+            require_breaking_change_footer: rules.require_breaking_change_footer,
+        }
+    }
+}
+
+// Granularity: API -> BLL
+impl From<crate::models::Granularity> for devpulse_core::models::Granularity {
+    fn from(granularity: crate::models::Granularity) -> Self {
+        match granularity {
+            crate::models::Granularity::Day => devpulse_core::models::Granularity::Day,
+            crate::models::Granularity::Week => devpulse_core::models::Granularity::Week,
+            crate::models::Granularity::Month => devpulse_core::models::Granularity::Month,
+        }
+    }
+}
+
+// CoAuthorCredit: API -> BLL
+impl From<crate::models::CoAuthorCredit> for devpulse_core::models::CoAuthorCredit {
+    fn from(credit: crate::models::CoAuthorCredit) -> Self {
+        match credit {
+            crate::models::CoAuthorCredit::Full => devpulse_core::models::CoAuthorCredit::Full,
+            crate::models::CoAuthorCredit::Split => devpulse_core::models::CoAuthorCredit::Split,
+        }
+    }
+}
+
+// ContributorRanking: API -> BLL
+impl From<crate::models::ContributorRanking> for devpulse_core::models::ContributorRanking {
+    fn from(ranking: crate::models::ContributorRanking) -> Self {
+        match ranking {
+            crate::models::ContributorRanking::Commits => {
+                devpulse_core::models::ContributorRanking::Commits
+            }
+            crate::models::ContributorRanking::Additions => {
+                devpulse_core::models::ContributorRanking::Additions
+            }
+            crate::models::ContributorRanking::Deletions => {
+                devpulse_core::models::ContributorRanking::Deletions
+            }
+            crate::models::ContributorRanking::Changes => {
+                devpulse_core::models::ContributorRanking::Changes
+            }
+            crate::models::ContributorRanking::FilesTouched => {
+                devpulse_core::models::ContributorRanking::FilesTouched
+            }
+        }
+    }
+}
+
 // // core/src/models/conversions.rs
 //
 // // CommitRangeAnalysis: BLL -> API
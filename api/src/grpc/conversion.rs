@@ -0,0 +1,132 @@
+use crate::grpc::server::proto;
+
+// CommitRangeRequest: gRPC -> BLL options
+impl From<&proto::CommitRangeRequest> for devpulse_core::models::CommitRangeOptions {
+    fn from(request: &proto::CommitRangeRequest) -> Self {
+        devpulse_core::models::CommitRangeOptions {
+            sections: request.sections().map(Into::into).collect(),
+            history_mode: request.history_mode().into(),
+            include_paths: request.include_paths.clone(),
+            exclude_paths: request.exclude_paths.clone(),
+            exclude_generated: !request.include_generated,
+            rank_contributors_by: request.rank_contributors_by().into(),
+            co_author_credit: request.co_author_credit().into(),
+            exclude_bots: request.exclude_bots,
+            bot_accounts: request.bot_accounts.clone(),
+            ..devpulse_core::models::CommitRangeOptions::default()
+        }
+    }
+}
+
+// CommitRangeRequest: gRPC -> BLL range
+impl From<&proto::CommitRangeRequest> for devpulse_core::models::RangeSpec {
+    fn from(request: &proto::CommitRangeRequest) -> Self {
+        // Proto3 strings cannot be absent, so an empty revision stands for none.
+        let revision = |revision: &str| (!revision.is_empty()).then(|| revision.to_string());
+        devpulse_core::models::RangeSpec {
+            start_commit: revision(&request.start_commit),
+            end_commit: revision(&request.end_commit),
+            revspec: request.revspec.clone(),
+            between_latest_tags: request.between_latest_tags,
+            since: request.since.clone(),
+            until: request.until.clone(),
+            last: request.last.map(|last| last as usize),
+            max_commits: request.max_commits.map(|max_commits| max_commits as usize),
+        }
+    }
+}
+
+// CommitRangeDetails: BLL -> gRPC
+impl From<devpulse_core::models::CommitRangeDetails> for proto::CommitRangeDetails {
+    fn from(details: devpulse_core::models::CommitRangeDetails) -> Self {
+        proto::CommitRangeDetails {
+            start_commit: details.start_commit,
+            end_commit: details.end_commit,
+            total_commits: details.total_commits,
+            total_additions: details.total_additions,
+            total_deletions: details.total_deletions,
+            top_contributors: details
+                .top_contributors
+                .into_iter()
+                .map(|contributor| proto::Contributor {
+                    username: contributor.username,
+                    commits: contributor.commits,
+                    email: contributor.email,
+                    additions: contributor.additions,
+                    deletions: contributor.deletions,
+                    files_touched: contributor.files_touched,
+                    is_bot: contributor.is_bot,
+                })
+                .collect(),
+        }
+    }
+}
+
+// AnalysisSection: gRPC -> BLL
+impl From<proto::AnalysisSection> for devpulse_core::models::AnalysisSection {
+    fn from(section: proto::AnalysisSection) -> Self {
+        match section {
+            proto::AnalysisSection::Files => devpulse_core::models::AnalysisSection::Files,
+            proto::AnalysisSection::Hotspots => devpulse_core::models::AnalysisSection::Hotspots,
+            proto::AnalysisSection::BusFactor => devpulse_core::models::AnalysisSection::BusFactor,
+            proto::AnalysisSection::Ownership => devpulse_core::models::AnalysisSection::Ownership,
+            proto::AnalysisSection::Rework => devpulse_core::models::AnalysisSection::Rework,
+            proto::AnalysisSection::Coupling => devpulse_core::models::AnalysisSection::Coupling,
+            proto::AnalysisSection::Activity => devpulse_core::models::AnalysisSection::Activity,
+            proto::AnalysisSection::WorkPatterns => {
+                devpulse_core::models::AnalysisSection::WorkPatterns
+            }
+            proto::AnalysisSection::Languages => devpulse_core::models::AnalysisSection::Languages,
+            proto::AnalysisSection::Compliance => {
+                devpulse_core::models::AnalysisSection::Compliance
+            }
+            proto::AnalysisSection::TestRatio => devpulse_core::models::AnalysisSection::TestRatio,
+            proto::AnalysisSection::Outliers => devpulse_core::models::AnalysisSection::Outliers,
+        }
+    }
+}
+
+// HistoryMode: gRPC -> BLL
+impl From<proto::HistoryMode> for devpulse_core::models::HistoryMode {
+    fn from(mode: proto::HistoryMode) -> Self {
+        match mode {
+            proto::HistoryMode::All => devpulse_core::models::HistoryMode::All,
+            proto::HistoryMode::FirstParent => devpulse_core::models::HistoryMode::FirstParent,
+            proto::HistoryMode::NoMerges => devpulse_core::models::HistoryMode::NoMerges,
+            proto::HistoryMode::MergesOnly => devpulse_core::models::HistoryMode::MergesOnly,
+        }
+    }
+}
+
+// CoAuthorCredit: gRPC -> BLL
+impl From<proto::CoAuthorCredit> for devpulse_core::models::CoAuthorCredit {
+    fn from(credit: proto::CoAuthorCredit) -> Self {
+        match credit {
+            proto::CoAuthorCredit::Full => devpulse_core::models::CoAuthorCredit::Full,
+            proto::CoAuthorCredit::Split => devpulse_core::models::CoAuthorCredit::Split,
+        }
+    }
+}
+
+// ContributorRanking: gRPC -> BLL
+impl From<proto::ContributorRanking> for devpulse_core::models::ContributorRanking {
+    fn from(ranking: proto::ContributorRanking) -> Self {
+        match ranking {
+            proto::ContributorRanking::Commits => {
+                devpulse_core::models::ContributorRanking::Commits
+            }
+            proto::ContributorRanking::Additions => {
+                devpulse_core::models::ContributorRanking::Additions
+            }
+            proto::ContributorRanking::Deletions => {
+                devpulse_core::models::ContributorRanking::Deletions
+            }
+            proto::ContributorRanking::Changes => {
+                devpulse_core::models::ContributorRanking::Changes
+            }
+            proto::ContributorRanking::FilesTouched => {
+                devpulse_core::models::ContributorRanking::FilesTouched
+            }
+        }
+    }
+}
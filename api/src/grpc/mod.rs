@@ -1 +1,2 @@
+mod conversion;
 pub(crate) mod server;
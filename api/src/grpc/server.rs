@@ -2,6 +2,12 @@
 
 use tonic::{transport::Server, Request, Response, Status};
 
+use devpulse_core::models::{
+    CommitRangeOptions, Connection, CustomRepository, OptionsError, RangeError, RangeSpec,
+    Repository,
+};
+use devpulse_core::services::analyze_commit_range_service;
+
 use crate::grpc::server::proto::dev_pulse_service_server::{
     DevPulseService, DevPulseServiceServer,
 };
@@ -23,11 +29,27 @@ impl DevPulseService for DevPulseServiceImpl {
     async fn analyze_commit_range(
         &self, request: Request<CommitRangeRequest>,
     ) -> Result<Response<CommitRangeResponse>, Status> {
-        // Implement your business logic here.
-        // For now, returning an empty response.
+        let request = request.into_inner();
+        let repository = Repository::Custom(CustomRepository {
+            url: request.repository_url.clone(),
+            connection: Connection::Https,
+        });
+        let range = RangeSpec::from(&request);
+        let options = CommitRangeOptions::from(&request);
+        let analysis = match analyze_commit_range_service(&repository, &range, &options).await {
+            Ok(analysis) => analysis,
+            Err(err) if err.is::<RangeError>() || err.is::<OptionsError>() => {
+                return Err(Status::invalid_argument(err.to_string()));
+            }
+            Err(err) => return Err(Status::internal(format!("Analysis error: {}", err))),
+        };
+
+        let analysis_json = serde_json::to_string(&analysis)
+            .map_err(|err| Status::internal(format!("Analysis error: {}", err)))?;
         let response = CommitRangeResponse {
-            repository: request.into_inner().repository_url,
-            commit_range: None, // Add proper CommitRangeDetails
+            repository: request.repository_url,
+            commit_range: Some(analysis.commit_range.into()),
+            analysis_json,
         };
         Ok(Response::new(response))
     }
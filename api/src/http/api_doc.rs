@@ -55,6 +55,8 @@ impl Modify for SecurityAddon {
     ),
     paths(
         crate::http::controllers::repository::create_commit_range_analysis,
+        crate::http::controllers::repository::create_changelog,
+        crate::http::controllers::repository::create_next_version,
         crate::http::controllers::developer::get_developer_performance,
         crate::http::controllers::pull_request::create_pull_request_analysis,
         crate::http::controllers::openapi::get_openapi_json,
@@ -71,6 +73,8 @@ impl Modify for SecurityAddon {
             crate::models::SourceVersionResponse,
             crate::models::NotImplemented,
             crate::models::CommitRangeAnalysisResponse,
+            crate::models::ChangelogResponse,
+            crate::models::VersionRecommendationResponse,
             crate::models::HealthCheckResponse,
         ),
         // headers(),
@@ -79,6 +83,12 @@ impl Modify for SecurityAddon {
             crate::models::HealthCheck,
             crate::models::CommitRangeRequest,
             crate::models::CommitRangeAnalysis,
+            crate::models::ChangelogRequest,
+            crate::models::ChangelogFormat,
+            crate::models::NextVersionRequest,
+            crate::models::VersionRecommendation,
+            crate::models::VersionBump,
+            crate::models::BumpCommit,
             crate::models::CommitRangeDetails,
             crate::models::ResponseDetail,
             crate::models::ResponseFormat,
@@ -86,6 +96,42 @@ impl Modify for SecurityAddon {
             crate::models::HealthCheckResponse,
             crate::models::DeveloperPerformanceAnalysis,
             crate::models::Contributor,
+            crate::models::ContributorRanking,
+            crate::models::CoAuthorCredit,
+            crate::models::HistoryMode,
+            crate::models::AnalysisSection,
+            crate::models::FileChurn,
+            crate::models::FileStatus,
+            crate::models::Hotspot,
+            crate::models::LanguageBreakdown,
+            crate::models::AuthorShare,
+            crate::models::DirectoryKnowledge,
+            crate::models::FileOwnership,
+            crate::models::Ownership,
+            crate::models::AuthorRework,
+            crate::models::FileRework,
+            crate::models::Rework,
+            crate::models::FileCoupling,
+            crate::models::Granularity,
+            crate::models::ActivityBucket,
+            crate::models::ActivitySeries,
+            crate::models::WorkingHours,
+            crate::models::CommitHistogram,
+            crate::models::AuthorWorkPattern,
+            crate::models::TeamWorkPattern,
+            crate::models::WorkPatterns,
+            crate::models::ComplianceRules,
+            crate::models::ComplianceRule,
+            crate::models::ComplianceViolation,
+            crate::models::CommitViolations,
+            crate::models::Compliance,
+            crate::models::CommitTestRatio,
+            crate::models::AuthorTestRatio,
+            crate::models::TestRatio,
+            crate::models::OutlierMetric,
+            crate::models::OutlierReason,
+            crate::models::OutlierCommit,
+            crate::models::Outliers,
             crate::errors::DevPulseError
         )
     )
@@ -0,0 +1,63 @@
+use axum::body::Body;
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+use axum::{response::IntoResponse, Json};
+
+use devpulse_core::models::{ChangelogError, ChangelogFormat, ChangelogOptions, RangeError};
+use devpulse_core::services::generate_changelog_service;
+
+use crate::models::{
+    BadRequest, ChangelogRequest, ChangelogResponse, InternalServerError, TooManyRequests,
+    Unauthorized,
+};
+
+/// Changelog
+///
+/// Generate a changelog of the conventional commits between two revisions, grouped by commit
+/// type and scope, in the requested format.
+#[utoipa::path(
+    put,
+    path = "/repository/changelog",
+    operation_id = "create_changelog",
+    responses(
+        (status = 200, response = ChangelogResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+    ),
+    request_body(
+        content = ChangelogRequest,
+        description = "The repository and the revisions to list the changes between",
+        content_type = "application/json",
+    ),
+    tag = "Repository",
+)]
+pub async fn create_changelog(Json(payload): Json<ChangelogRequest>) -> Response<Body> {
+    let options = ChangelogOptions::from(&payload);
+    match generate_changelog_service(
+        &payload.repository.into(),
+        &payload.start_commit,
+        &payload.end_commit,
+        &options,
+    )
+    .await
+    {
+        Ok(changelog) => {
+            let content_type = match options.format {
+                ChangelogFormat::Json => "application/json",
+                ChangelogFormat::Markdown | ChangelogFormat::KeepAChangelog => {
+                    "text/markdown; charset=utf-8"
+                }
+            };
+            (StatusCode::OK, [(header::CONTENT_TYPE, content_type)], changelog).into_response()
+        }
+        Err(err) if err.is::<ChangelogError>() || err.is::<RangeError>() => {
+            BadRequest::new(&err.to_string()).into_response()
+        }
+        Err(err) => {
+            let error_message = format!("Changelog error: {}", err);
+            InternalServerError::new(&error_message).into_response()
+        }
+    }
+}
@@ -5,6 +5,7 @@ use axum::response::Response;
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
+use devpulse_core::models::{CommitRangeOptions, OptionsError, RangeError, RangeSpec};
 use devpulse_core::services::analyze_commit_range_service;
 
 use crate::accept::serialize_response;
@@ -59,14 +60,13 @@ pub async fn create_commit_range_analysis(
     Json(payload): Json<CommitRangeRequest>,
 ) -> Response<Body> {
     let _params = params.unwrap_or_default();
-    match analyze_commit_range_service(
-        &payload.repository.into(),
-        &payload.start_commit,
-        &payload.end_commit,
-    )
-    .await
-    {
+    let range = RangeSpec::from(&payload);
+    let options = CommitRangeOptions::from(&payload);
+    match analyze_commit_range_service(&payload.repository.into(), &range, &options).await {
         Ok(result) => serialize_response(&result, &headers),
+        Err(err) if err.is::<RangeError>() || err.is::<OptionsError>() => {
+            BadRequest::new(&err.to_string()).into_response()
+        }
         Err(err) => {
             let error_message = format!("Analysis error: {}", err);
             InternalServerError::new(&error_message).into_response()
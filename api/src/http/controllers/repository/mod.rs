@@ -1,3 +1,7 @@
+mod changelog;
 mod commit_range;
+mod next_version;
 
+pub use changelog::*;
 pub use commit_range::*;
+pub use next_version::*;
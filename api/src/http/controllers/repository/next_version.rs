@@ -0,0 +1,49 @@
+use axum::body::Body;
+use axum::http::HeaderMap;
+use axum::response::Response;
+use axum::{response::IntoResponse, Json};
+
+use devpulse_core::models::{ReleaseError, ReleaseOptions};
+use devpulse_core::services::recommend_version_service;
+
+use crate::accept::serialize_response;
+use crate::models::{
+    BadRequest, InternalServerError, NextVersionRequest, TooManyRequests, Unauthorized,
+    VersionRecommendationResponse,
+};
+
+/// Next Version
+///
+/// Recommend the next semantic version of a repository from the conventional commits since its
+/// latest release tag, along with the commits that call for the bump.
+#[utoipa::path(
+    put,
+    path = "/repository/next-version",
+    operation_id = "create_next_version",
+    responses(
+        (status = 200, response = VersionRecommendationResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 429, response = TooManyRequests),
+        (status = 500, response = InternalServerError),
+    ),
+    request_body(
+        content = NextVersionRequest,
+        description = "The repository and the revision to be released",
+        content_type = "application/json",
+    ),
+    tag = "Repository",
+)]
+pub async fn create_next_version(
+    headers: HeaderMap, Json(payload): Json<NextVersionRequest>,
+) -> Response<Body> {
+    let options = ReleaseOptions::from(&payload);
+    match recommend_version_service(&payload.repository.into(), &payload.target, &options).await {
+        Ok(result) => serialize_response(&result, &headers),
+        Err(err) if err.is::<ReleaseError>() => BadRequest::new(&err.to_string()).into_response(),
+        Err(err) => {
+            let error_message = format!("Version error: {}", err);
+            InternalServerError::new(&error_message).into_response()
+        }
+    }
+}
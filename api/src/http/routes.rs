@@ -97,6 +97,18 @@ pub(crate) fn create_router() -> Router {
                 controllers::repository::__path_create_commit_range_analysis::path().as_str(),
             ),
             put(controllers::repository::create_commit_range_analysis),
+        )
+        .route(
+            &*crate::utils::convert_openapi_to_axum_path(
+                controllers::repository::__path_create_changelog::path().as_str(),
+            ),
+            put(controllers::repository::create_changelog),
+        )
+        .route(
+            &*crate::utils::convert_openapi_to_axum_path(
+                controllers::repository::__path_create_next_version::path().as_str(),
+            ),
+            put(controllers::repository::create_next_version),
         );
 
     let router = if std::env::var("SHUTTLE").is_ok() {
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::SystemTime;
 
@@ -24,10 +25,138 @@ pub struct CommitRangeRequest {
         "name": "rules_rust"
     }))]
     pub repository: Repository,
+    /// The revision the range starts after, exclusive. The range covers the whole history of
+    /// its end when omitted.
     #[schema(example = "6c2bd67")]
-    pub start_commit: String,
+    pub start_commit: Option<String>,
+    /// The revision the range ends at, inclusive. Defaults to `HEAD`.
     #[schema(example = "6b10ce3")]
-    pub end_commit: String,
+    pub end_commit: Option<String>,
+    /// A revspec such as `v1.2.0..main`, or a single revision such as `HEAD~50` for its whole
+    /// history. Cannot be combined with `start_commit`, `end_commit` or
+    /// `between_latest_tags`.
+    #[schema(example = "v1.2.0..main")]
+    pub revspec: Option<String>,
+    /// Whether the range goes from the second most recent tag to the most recent one. Cannot
+    /// be combined with `start_commit`, `end_commit` or `revspec`.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub between_latest_tags: bool,
+    /// Only commits made at or after this time, as an RFC 3339 timestamp or a `YYYY-MM-DD` date
+    /// in UTC.
+    #[schema(example = "2024-01-01")]
+    pub since: Option<String>,
+    /// Only commits made at or before this time, as an RFC 3339 timestamp or a `YYYY-MM-DD` date
+    /// in UTC, which includes the whole day.
+    #[schema(example = "2024-06-30T23:59:59Z")]
+    pub until: Option<String>,
+    /// Only the given number of most recent commits of the range.
+    #[schema(example = 50)]
+    pub last: Option<usize>,
+    /// The most commits the range may hold. Larger ranges are rejected with a 400.
+    #[schema(example = 20000)]
+    pub max_commits: Option<usize>,
+    #[serde(default)]
+    #[schema(example = json!(["files"]))]
+    pub sections: Vec<AnalysisSection>,
+    /// Which commits of the range are analyzed. Defaults to `all`.
+    #[schema(example = "first_parent")]
+    pub history_mode: Option<HistoryMode>,
+    /// Globs selecting the files to analyze. Every file is analyzed when empty.
+    #[serde(default)]
+    #[schema(example = json!(["src/**"]))]
+    pub include_paths: Vec<String>,
+    /// Globs selecting files to leave out of the analysis.
+    #[serde(default)]
+    #[schema(example = json!(["docs/**"]))]
+    pub exclude_paths: Vec<String>,
+    /// Whether to leave lockfiles and vendored or generated files out of the analysis,
+    /// honouring `linguist-generated` and `linguist-vendored` in `.gitattributes`. Defaults to
+    /// true.
+    #[schema(example = true)]
+    pub exclude_generated: Option<bool>,
+    /// How many directory levels deep the bus factor tree goes. Defaults to 2.
+    #[schema(example = 2)]
+    pub bus_factor_depth: Option<usize>,
+    /// How many days after landing a rewritten line still counts as rework. Defaults to 21.
+    #[schema(example = 21)]
+    pub rework_window_days: Option<u32>,
+    /// How many commits must change a pair of files for it to count as coupled. Defaults to 3.
+    #[schema(example = 3)]
+    pub coupling_min_co_changes: Option<usize>,
+    /// Maps author names or emails to the canonical email they belong to, on top of `.mailmap`.
+    #[serde(default)]
+    #[schema(example = json!({"jane@laptop.local": "jane@example.com"}))]
+    pub aliases: HashMap<String, String>,
+    /// The metric used to rank the top contributors. Defaults to `commits`.
+    #[schema(example = "commits")]
+    pub rank_contributors_by: Option<ContributorRanking>,
+    /// How commits with `Co-authored-by:` trailers are credited. Defaults to `full`.
+    #[schema(example = "split")]
+    pub co_author_credit: Option<CoAuthorCredit>,
+    /// Whether to leave commits authored by bots out of the analysis.
+    #[serde(default, alias = "include_only_humans")]
+    #[schema(example = true)]
+    pub exclude_bots: bool,
+    /// Names or emails of additional accounts to treat as bots.
+    #[serde(default)]
+    #[schema(example = json!(["release-robot@example.com"]))]
+    pub bot_accounts: Vec<String>,
+    /// The calendar period that the activity series is bucketed by. Defaults to `week`.
+    #[schema(example = "month")]
+    pub granularity: Option<Granularity>,
+    /// The IANA timezone used to assign commits to calendar periods. Defaults to `UTC`.
+    #[schema(example = "Europe/Amsterdam")]
+    pub timezone: Option<String>,
+    /// The hours outside of which commits are flagged as after-hours work. Defaults to 9 to 18
+    /// on weekdays.
+    pub working_hours: Option<WorkingHours>,
+    /// Team names mapped to the canonical emails of their members.
+    #[serde(default)]
+    #[schema(example = json!({"rules": ["daniel@example.com", "milan@example.com"]}))]
+    pub teams: HashMap<String, Vec<String>>,
+    /// The rules that commit messages are checked against. Defaults to the Angular commit
+    /// types and subjects of at most 72 characters.
+    pub compliance_rules: Option<ComplianceRules>,
+    /// Globs marking the test files of a language, replacing the built-in conventions of that
+    /// language.
+    #[serde(default)]
+    #[schema(example = json!({"Go": ["**/*_test.go", "**/testdata/**"]}))]
+    pub test_patterns: HashMap<String, Vec<String>>,
+    /// The modified z-score, against the median and median absolute deviation of the range,
+    /// above which a commit is flagged as an outlier. Defaults to 3.5.
+    #[schema(example = 3.5)]
+    pub outlier_threshold: Option<f64>,
+}
+
+/// An optional section that can be requested in a commit range analysis.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisSection {
+    /// Per-file churn for every commit in the range.
+    Files,
+    /// Files ranked by change frequency and churn.
+    Hotspots,
+    /// A directory tree of knowledge concentration and bus factors.
+    BusFactor,
+    /// Blame-based ownership of the surviving lines at the end commit.
+    Ownership,
+    /// Lines added in the range that were rewritten or deleted soon after landing.
+    Rework,
+    /// Pairs of files that are frequently modified in the same commit.
+    Coupling,
+    /// Commits, line changes and active authors bucketed by calendar period.
+    Activity,
+    /// Commits by hour of day and day of week, in each author's local time.
+    WorkPatterns,
+    /// Churn, files and authors per programming language.
+    Languages,
+    /// How well commit messages follow the Conventional Commits rules.
+    Compliance,
+    /// Test churn versus production churn, and commits that change production code untested.
+    TestRatio,
+    /// Commits with unusually large diffs, mass deletions, many files or added binaries.
+    Outliers,
 }
 
 /// Represents the response containing the results from analyzing a commit range.
@@ -46,12 +175,60 @@ pub struct CommitRangeAnalysis {
         "total_additions": 1163,
         "total_deletions": 59,
         "top_contributors": [
-            {"username": "Daniel Wagner-Hall", "commits": 1144},
-            {"username": "Milan Vukov", "commits": 60},
-            {"username": "Marcel Hlopko", "commits": 18}
+            {
+                "username": "Daniel Wagner-Hall",
+                "email": "daniel@example.com",
+                "commits": 3,
+                "additions": 1102,
+                "deletions": 41,
+                "files_touched": 12,
+                "is_bot": false
+            },
+            {
+                "username": "Milan Vukov",
+                "email": "milan@example.com",
+                "commits": 2,
+                "additions": 52,
+                "deletions": 8,
+                "files_touched": 3,
+                "is_bot": false
+            },
+            {
+                "username": "Marcel Hlopko",
+                "email": "marcel@example.com",
+                "commits": 1,
+                "additions": 9,
+                "deletions": 10,
+                "files_touched": 2,
+                "is_bot": false
+            }
         ]
     }))]
     pub commit_range: CommitRangeDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileChurn>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotspots: Option<Vec<Hotspot>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_factor: Option<DirectoryKnowledge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<Ownership>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rework: Option<Rework>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupling: Option<Vec<FileCoupling>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<ActivitySeries>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_patterns: Option<WorkPatterns>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<LanguageBreakdown>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<Compliance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_ratio: Option<TestRatio>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outliers: Option<Outliers>,
 }
 
 create_response_enum!(CommitRangeAnalysisResponse, "Commit Range Analysis", CommitRangeAnalysis);
@@ -61,10 +238,174 @@ impl CommitRangeAnalysis {
         CommitRangeAnalysis {
             repository: repository.clone(),
             commit_range,
+            files: None,
+            hotspots: None,
+            bus_factor: None,
+            ownership: None,
+            rework: None,
+            coupling: None,
+            activity: None,
+            work_patterns: None,
+            languages: None,
+            compliance: None,
+            test_ratio: None,
+            outliers: None,
         }
     }
 }
 
+/// Represents a request to generate a changelog between two revisions of a repository.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ChangelogRequest {
+    #[schema(example = json!({
+        "type": "github",
+        "owner": "bazelbuild",
+        "name": "rules_rust"
+    }))]
+    pub repository: Repository,
+    /// The revision the changes are listed from, exclusive, such as the previous release tag.
+    #[schema(example = "0.48.0")]
+    pub start_commit: String,
+    /// The revision the changes are listed up to, inclusive.
+    #[schema(example = "main")]
+    pub end_commit: String,
+    /// The format the changelog is rendered in. Defaults to `markdown`.
+    #[schema(example = "keep_a_changelog")]
+    pub format: Option<ChangelogFormat>,
+    /// A handlebars template replacing the built-in one of the format. Templates can use every
+    /// field of the changelog, the entries grouped into the `categories` of Keep a Changelog,
+    /// the `{{> entry}}` partial and the `{{json value}}` helper.
+    #[schema(
+        example = "{{#each sections}}# {{title}}\n{{#each scopes}}{{#each entries}}- {{> entry}}\n{{/each}}{{/each}}{{/each}}"
+    )]
+    pub template: Option<String>,
+    /// The version the changes are released in. Defaults to unreleased.
+    #[schema(example = "0.49.0")]
+    pub version: Option<String>,
+    /// Which commits of the range are listed. Defaults to `all`.
+    #[schema(example = "first_parent")]
+    pub history_mode: Option<HistoryMode>,
+    /// Whether to leave commits authored by bots out of the changelog.
+    #[serde(default)]
+    #[schema(example = true)]
+    pub exclude_bots: bool,
+    /// Names or emails of additional accounts to treat as bots.
+    #[serde(default)]
+    #[schema(example = json!(["release-robot@example.com"]))]
+    pub bot_accounts: Vec<String>,
+    /// Maps author names or emails to the canonical email they belong to, on top of `.mailmap`.
+    #[serde(default)]
+    #[schema(example = json!({"jane@laptop.local": "jane@example.com"}))]
+    pub aliases: HashMap<String, String>,
+}
+
+/// The formats a changelog can be rendered in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogFormat {
+    /// Markdown sections per commit type, in the style of `conventional-changelog`.
+    Markdown,
+    /// The changelog data as JSON.
+    Json,
+    /// A release section following <https://keepachangelog.com>.
+    KeepAChangelog,
+}
+
+/// A changelog rendered in the requested format.
+#[derive(ToResponse)]
+#[response(description = "Changelog")]
+pub(crate) enum ChangelogResponse {
+    Markdown(
+        #[content("text/markdown")]
+        #[schema(
+            example = "## 0.49.0 (2024-07-18)\n\n### Features\n\n- **bzlmod:** support \
+                            crate annotations ([6b10ce3](https://github.com/bazelbuild/rules_rust/commit/6b10ce3), \
+                            [#2714](https://github.com/bazelbuild/rules_rust/pull/2714)) by Daniel Wagner-Hall\n"
+        )]
+        String,
+    ),
+    Json(#[content("application/json")] String),
+}
+
+/// Represents a request to recommend the next version of a repository.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct NextVersionRequest {
+    #[schema(example = json!({
+        "type": "github",
+        "owner": "bazelbuild",
+        "name": "rules_rust"
+    }))]
+    pub repository: Repository,
+    /// The revision to be released, such as a branch or a commit.
+    #[schema(example = "main")]
+    pub target: String,
+    /// The pre-release identifier to release under, such as `rc` for `2.0.0-rc.1`. Defaults
+    /// to a stable release.
+    #[schema(example = "rc")]
+    pub pre_release: Option<String>,
+    /// The prefix of release tags, such as `core-` for `core-v1.2.3`. Defaults to none.
+    #[schema(example = "")]
+    pub tag_prefix: Option<String>,
+    /// Which commits since the latest release are considered. Defaults to `all`.
+    #[schema(example = "first_parent")]
+    pub history_mode: Option<HistoryMode>,
+}
+
+/// How much a version is bumped.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Represents a conventional commit that calls for a version bump.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct BumpCommit {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "feat")]
+    pub commit_type: String,
+    #[schema(example = "bzlmod")]
+    pub scope: Option<String>,
+    #[schema(example = "support crate annotations")]
+    pub description: String,
+    /// Whether the commit is marked as breaking or has a `BREAKING CHANGE` footer.
+    #[schema(example = false)]
+    pub breaking: bool,
+}
+
+/// Represents the recommended next version of a repository, following Semantic Versioning.
+///
+/// Breaking changes bump the major version, features the minor version and fixes and
+/// performance improvements the patch version. While the major version is 0, breaking changes
+/// only bump the minor version.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct VersionRecommendation {
+    /// The latest stable release tag reachable from the target, if any.
+    #[schema(example = "0.48.0")]
+    pub current_tag: Option<String>,
+    #[schema(example = "0.48.0")]
+    pub current_version: Option<String>,
+    /// The recommended next version, which is the current version when nothing calls for a
+    /// release.
+    #[schema(example = "0.49.0-rc.1")]
+    pub next_version: String,
+    /// The bump from the current to the next version, or `null` when nothing calls for a
+    /// release.
+    #[schema(example = "minor")]
+    pub bump: Option<VersionBump>,
+    /// The commits since the current release that call for the bump, newest first.
+    pub commits: Vec<BumpCommit>,
+}
+
+create_response_enum!(
+    VersionRecommendationResponse,
+    "Version Recommendation",
+    VersionRecommendation
+);
+
 /// Details the results of a commit range analysis, including commits, additions, deletions, and contributors.
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct CommitRangeDetails {
@@ -79,9 +420,33 @@ pub struct CommitRangeDetails {
     #[schema(example = "59")]
     pub total_deletions: i32,
     #[schema(example = json!([
-        {"username": "Daniel Wagner-Hall", "commits": 1144},
-        {"username": "Milan Vukov", "commits": 60},
-        {"username": "Marcel Hlopko", "commits": 18}
+        {
+            "username": "Daniel Wagner-Hall",
+            "email": "daniel@example.com",
+            "commits": 3,
+            "additions": 1102,
+            "deletions": 41,
+            "files_touched": 12,
+            "is_bot": false
+        },
+        {
+            "username": "Milan Vukov",
+            "email": "milan@example.com",
+            "commits": 2,
+            "additions": 52,
+            "deletions": 8,
+            "files_touched": 3,
+            "is_bot": false
+        },
+        {
+            "username": "Marcel Hlopko",
+            "email": "marcel@example.com",
+            "commits": 1,
+            "additions": 9,
+            "deletions": 10,
+            "files_touched": 2,
+            "is_bot": false
+        }
     ]))]
     pub top_contributors: Vec<Contributor>,
 }
@@ -90,7 +455,555 @@ pub struct CommitRangeDetails {
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct Contributor {
     pub username: String,
+    /// The canonical email identifying the contributor, after `.mailmap` and alias resolution.
+    pub email: String,
+    pub commits: i32,
+    pub additions: i32,
+    pub deletions: i32,
+    /// The number of distinct files the contributor changed.
+    pub files_touched: i32,
+    /// Whether the contributor is an automation account rather than a person.
+    pub is_bot: bool,
+}
+
+/// How the work of a commit is credited when it has `Co-authored-by:` trailers.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CoAuthorCredit {
+    /// The author and every co-author each get full credit for the commit.
+    Full,
+    /// The commit's counts are split equally between the author and co-authors.
+    Split,
+}
+
+/// Which commits of a range are analyzed, and how merge commits are treated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryMode {
+    /// Every commit reachable in the range.
+    All,
+    /// Only commits on the first-parent chain, i.e. what landed on the main branch.
+    FirstParent,
+    /// Every commit in the range except merge commits.
+    NoMerges,
+    /// Only merge commits on the first-parent chain, each counted as a single squashed change.
+    MergesOnly,
+}
+
+/// The metric used to rank contributors.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContributorRanking {
+    Commits,
+    Additions,
+    Deletions,
+    /// Additions plus deletions.
+    Changes,
+    FilesTouched,
+}
+
+/// The kind of change a commit made to a file.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// Represents the churn of a single file within a commit.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FileChurn {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "rust/private/rustc.bzl")]
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub status: FileStatus,
+    #[schema(example = "42")]
+    pub additions: i32,
+    #[schema(example = "7")]
+    pub deletions: i32,
+    pub binary: bool,
+}
+
+/// Represents how often and how heavily a file changed over a commit range.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Hotspot {
+    #[schema(example = "rust/private/rustc.bzl")]
+    pub path: String,
+    #[schema(example = "4")]
+    pub changes: i32,
+    #[schema(example = "120")]
+    pub additions: i32,
+    #[schema(example = "35")]
+    pub deletions: i32,
+    #[schema(example = "155")]
+    pub churn: i32,
+    #[schema(example = "3")]
+    pub authors: i32,
+    #[schema(example = "2024-07-18T09:12:44Z")]
+    pub last_modified: String,
+}
+
+/// Represents the churn, files and authors of one language over a commit range.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct LanguageBreakdown {
+    /// The language name, or `Other` for files that could not be classified.
+    #[schema(example = "Rust")]
+    pub language: String,
+    #[schema(example = "18")]
+    pub commits: i32,
+    #[schema(example = "640")]
+    pub additions: i32,
+    #[schema(example = "212")]
+    pub deletions: i32,
+    #[schema(example = "852")]
+    pub churn: i32,
+    #[schema(example = "27")]
+    pub files: i32,
+    #[schema(example = "5")]
+    pub authors: i32,
+}
+
+/// Represents the share of lines an author contributed to a part of the repository.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AuthorShare {
+    #[schema(example = "Daniel Wagner-Hall")]
+    pub author: String,
+    #[schema(example = "daniel@example.com")]
+    pub email: String,
+    #[schema(example = "1144")]
+    pub lines: i32,
+    #[schema(example = "0.92")]
+    pub share: f64,
+}
+
+/// Represents how knowledge of a directory is concentrated among its authors.
+///
+/// The bus factor is the minimum number of authors whose departure would leave most of the
+/// directory's lines unowned.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DirectoryKnowledge {
+    #[schema(example = "rust/private")]
+    pub path: String,
+    #[schema(example = "1163")]
+    pub lines: i32,
+    #[schema(example = "1")]
+    pub bus_factor: i32,
+    pub authors: Vec<AuthorShare>,
+    pub children: Vec<DirectoryKnowledge>,
+}
+
+/// Represents who owns the surviving lines of a single file.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FileOwnership {
+    #[schema(example = "rust/private/rustc.bzl")]
+    pub path: String,
+    #[schema(example = "412")]
+    pub lines: i32,
+    pub authors: Vec<AuthorShare>,
+}
+
+/// Represents who owns the surviving lines of a repository at the end commit, according to blame.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Ownership {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "28734")]
+    pub lines: i32,
+    pub authors: Vec<AuthorShare>,
+    pub files: Vec<FileOwnership>,
+}
+
+/// Represents how much of an author's new code was rewritten or deleted soon after it landed.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AuthorRework {
+    #[schema(example = "Daniel Wagner-Hall")]
+    pub author: String,
+    #[schema(example = "daniel@example.com")]
+    pub email: String,
+    #[schema(example = "940")]
+    pub added: i32,
+    #[schema(example = "112")]
+    pub reworked: i32,
+    #[schema(example = "0.12")]
+    pub rework_ratio: f64,
+}
+
+/// Represents how much of the new code in a file was rewritten or deleted soon after it landed.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FileRework {
+    #[schema(example = "rust/private/rustc.bzl")]
+    pub path: String,
+    #[schema(example = "211")]
+    pub added: i32,
+    #[schema(example = "48")]
+    pub reworked: i32,
+    #[schema(example = "0.23")]
+    pub rework_ratio: f64,
+}
+
+/// Represents code survival within a commit range.
+///
+/// A line counts as reworked when it was added inside the range and then rewritten or deleted
+/// within `window_days` of being added.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Rework {
+    #[schema(example = "21")]
+    pub window_days: i32,
+    #[schema(example = "1163")]
+    pub added: i32,
+    #[schema(example = "131")]
+    pub reworked: i32,
+    #[schema(example = "0.11")]
+    pub rework_ratio: f64,
+    pub authors: Vec<AuthorRework>,
+    pub files: Vec<FileRework>,
+}
+
+/// Represents two files that tend to change in the same commits.
+///
+/// Reads as "when `path` changes, `coupled_path` changes too with probability `confidence`".
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct FileCoupling {
+    #[schema(example = "rust/private/rustc.bzl")]
+    pub path: String,
+    #[schema(example = "rust/private/rust.bzl")]
+    pub coupled_path: String,
+    #[schema(example = "12")]
+    pub co_changes: i32,
+    #[schema(example = "0.08")]
+    pub support: f64,
+    #[schema(example = "0.75")]
+    pub confidence: f64,
+}
+
+/// The calendar period that activity is bucketed by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Day,
+    /// Weeks starting on Monday.
+    Week,
+    Month,
+}
+
+/// Represents the activity within a single calendar period.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ActivityBucket {
+    /// The first day of the period, in the series' timezone.
+    #[schema(example = "2024-07-01")]
+    pub start: String,
+    #[schema(example = "4")]
     pub commits: i32,
+    #[schema(example = "812")]
+    pub additions: i32,
+    #[schema(example = "37")]
+    pub deletions: i32,
+    #[schema(example = "2")]
+    pub active_authors: i32,
+}
+
+/// Represents activity over a commit range as a series of consecutive calendar periods.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ActivitySeries {
+    pub granularity: Granularity,
+    #[schema(example = "Europe/Amsterdam")]
+    pub timezone: String,
+    pub buckets: Vec<ActivityBucket>,
+}
+
+/// The hours considered normal working time, in each author's local time.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WorkingHours {
+    /// The first working hour of the day, from 0 to 23.
+    #[schema(example = "9")]
+    pub start_hour: u32,
+    /// The hour at which the working day ends, exclusive.
+    #[schema(example = "18")]
+    pub end_hour: u32,
+    /// Whether Saturday and Sunday are working days.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub weekends: bool,
+}
+
+/// Represents when commits were made, in their authors' local time.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommitHistogram {
+    #[schema(example = "6")]
+    pub commits: i32,
+    /// Commits per hour of the day, from midnight.
+    #[schema(example = json!([
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0,
+        0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0
+    ]))]
+    pub by_hour: Vec<i32>,
+    /// Commits per day of the week, from Monday.
+    #[schema(example = json!([2, 1, 0, 1, 0, 2, 0]))]
+    pub by_weekday: Vec<i32>,
+    /// Commits made outside of working hours.
+    #[schema(example = "3")]
+    pub after_hours: i32,
+    #[schema(example = "0.5")]
+    pub after_hours_ratio: f64,
+}
+
+/// Represents when an author commits.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AuthorWorkPattern {
+    #[schema(example = "Daniel Wagner-Hall")]
+    pub author: String,
+    #[schema(example = "daniel@example.com")]
+    pub email: String,
+    pub histogram: CommitHistogram,
+}
+
+/// Represents when the members of a team commit.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TeamWorkPattern {
+    #[schema(example = "rules")]
+    pub team: String,
+    pub histogram: CommitHistogram,
+}
+
+/// Represents the distribution of commits over the hours of the day and days of the week.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WorkPatterns {
+    pub working_hours: WorkingHours,
+    /// The distribution over every author in the range.
+    pub overall: CommitHistogram,
+    pub authors: Vec<AuthorWorkPattern>,
+    pub teams: Vec<TeamWorkPattern>,
+}
+
+/// The rules that commit messages are checked against, on top of the Conventional Commits
+/// format itself.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ComplianceRules {
+    /// The allowed commit types, compared ignoring case. Any type is allowed when empty.
+    /// Defaults to the types of the Angular convention.
+    #[schema(example = json!(["feat", "fix", "docs", "refactor", "test", "chore"]))]
+    pub allowed_types: Option<Vec<String>>,
+    /// Whether every commit must name a scope, as in `fix(parser): ...`.
+    #[serde(default)]
+    #[schema(example = true)]
+    pub require_scope: bool,
+    /// The maximum number of characters in the subject line. Defaults to 72, and 0 turns the
+    /// limit off.
+    #[schema(example = "72")]
+    pub max_subject_length: Option<usize>,
+    /// Whether commits marked as breaking with `!` must also have a `BREAKING CHANGE` footer.
+    #[serde(default)]
+    #[schema(example = true)]
+    pub require_breaking_change_footer: bool,
+}
+
+/// A rule that a commit message can break.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceRule {
+    /// The message is not a valid conventional commit.
+    Format,
+    /// The commit type is not one of the allowed types.
+    Type,
+    /// The commit does not name a scope.
+    Scope,
+    /// The subject line is too long.
+    SubjectLength,
+    /// The commit is marked as breaking but has no `BREAKING CHANGE` footer.
+    BreakingChangeFooter,
+}
+
+/// Represents a rule broken by a commit message.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ComplianceViolation {
+    pub rule: ComplianceRule,
+    #[schema(example = "the scope is missing")]
+    pub message: String,
+}
+
+/// Represents a commit whose message broke at least one rule.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommitViolations {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "Milan Vukov")]
+    pub author: String,
+    #[schema(example = "milan@example.com")]
+    pub email: String,
+    /// The first line of the commit message.
+    #[schema(example = "fix: handle empty crate names")]
+    pub subject: String,
+    pub violations: Vec<ComplianceViolation>,
+}
+
+/// Represents how well the commit messages of a range follow the Conventional Commits rules.
+///
+/// Messages generated by git, such as those of merges, reverts and fixups, are not checked.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Compliance {
+    #[schema(example = "6")]
+    pub checked: i32,
+    #[schema(example = "5")]
+    pub compliant: i32,
+    #[schema(example = "0.83")]
+    pub compliance_rate: f64,
+    /// The commits that broke at least one rule, newest first.
+    pub commits: Vec<CommitViolations>,
+}
+
+/// Represents the test and production churn of a commit.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct CommitTestRatio {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "Milan Vukov")]
+    pub author: String,
+    #[schema(example = "milan@example.com")]
+    pub email: String,
+    #[schema(example = "0")]
+    pub test_additions: usize,
+    #[schema(example = "0")]
+    pub test_deletions: usize,
+    #[schema(example = "12")]
+    pub production_additions: usize,
+    #[schema(example = "3")]
+    pub production_deletions: usize,
+    /// Whether the commit changed production code without changing any test code.
+    #[schema(example = true)]
+    pub untested: bool,
+}
+
+/// Represents the test and production churn of an author.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct AuthorTestRatio {
+    #[schema(example = "Daniel Wagner-Hall")]
+    pub author: String,
+    #[schema(example = "daniel@example.com")]
+    pub email: String,
+    /// The commits of the author that changed code.
+    #[schema(example = "3")]
+    pub commits: usize,
+    #[schema(example = "1")]
+    pub untested_commits: usize,
+    #[schema(example = "420")]
+    pub test_additions: usize,
+    #[schema(example = "12")]
+    pub test_deletions: usize,
+    #[schema(example = "682")]
+    pub production_additions: usize,
+    #[schema(example = "29")]
+    pub production_deletions: usize,
+    /// The test churn divided by the production churn, or null when no production code
+    /// changed.
+    #[schema(example = "0.61")]
+    pub test_ratio: Option<f64>,
+}
+
+/// Represents how much test code changed alongside production code within a range of commits.
+///
+/// Only files of languages with test conventions count as code. Commits that change no code
+/// are left out.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TestRatio {
+    #[schema(example = "431")]
+    pub test_additions: usize,
+    #[schema(example = "15")]
+    pub test_deletions: usize,
+    #[schema(example = "720")]
+    pub production_additions: usize,
+    #[schema(example = "42")]
+    pub production_deletions: usize,
+    /// The test churn divided by the production churn, or null when no production code
+    /// changed.
+    #[schema(example = "0.59")]
+    pub test_ratio: Option<f64>,
+    #[schema(example = "2")]
+    pub untested_commits: usize,
+    pub authors: Vec<AuthorTestRatio>,
+    /// The commits that changed code, newest first.
+    pub commits: Vec<CommitTestRatio>,
+}
+
+/// A way in which a commit can stand out from the usual commits of its repository.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlierMetric {
+    /// The commit added and deleted far more lines than usual.
+    Churn,
+    /// The commit deleted far more lines than usual, and more than it added.
+    Deletions,
+    /// The commit touched far more files than usual.
+    Files,
+    /// The commit added binary files.
+    BinaryFiles,
+}
+
+/// Represents a reason a commit was flagged as an outlier.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct OutlierReason {
+    #[schema(example = "churn")]
+    pub metric: OutlierMetric,
+    /// The value of the metric for the commit.
+    #[schema(example = "4120")]
+    pub value: usize,
+    /// The median of the metric over the baseline, or null for binary files, which are
+    /// flagged whenever they are added.
+    #[schema(example = "36")]
+    pub median: Option<f64>,
+    /// The modified z-score of the commit, the number of robust standard deviations it lies
+    /// above the median, or null for binary files.
+    #[schema(example = "5.8")]
+    pub score: Option<f64>,
+    #[schema(example = "changed 4120 lines, against a median of 36")]
+    pub message: String,
+}
+
+/// Represents a commit that stands out from the usual commits of its repository.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct OutlierCommit {
+    #[schema(example = "6b10ce3")]
+    pub commit: String,
+    #[schema(example = "Daniel Wagner-Hall")]
+    pub author: String,
+    #[schema(example = "daniel@example.com")]
+    pub email: String,
+    /// The first line of the commit message.
+    #[schema(example = "chore: vendor crate index")]
+    pub subject: String,
+    #[schema(example = "4102")]
+    pub additions: usize,
+    #[schema(example = "18")]
+    pub deletions: usize,
+    #[schema(example = "7")]
+    pub files: usize,
+    pub reasons: Vec<OutlierReason>,
+}
+
+/// Represents the statistically unusual commits of a range, which skew its averages.
+///
+/// Commits are measured against the median and median absolute deviation of a baseline, the
+/// most recent commits of the history up to the end of the range, so that what counts as
+/// unusual follows the habits of the repository even for a short range.
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct Outliers {
+    /// The number of commits measured, which changed at least one selected file.
+    #[schema(example = "120")]
+    pub measured: usize,
+    /// The number of commits of the baseline, the most recent ones up to the end of the range
+    /// that changed at least one selected file. Only binary files are flagged when it is too
+    /// small to tell what is usual.
+    #[schema(example = "200")]
+    pub baseline: usize,
+    /// The modified z-score above which a commit is flagged.
+    #[schema(example = "3.5")]
+    pub threshold: f64,
+    /// The flagged commits, newest first.
+    pub commits: Vec<OutlierCommit>,
 }
 
 /// Represents a developer's performance metrics.
@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate};
+use chrono_tz::Tz;
+
+use crate::models::{ActivityBucket, ActivitySeries, Granularity};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Buckets commits, line changes and active authors by calendar period.
+pub struct ActivityAnalyzer {
+    granularity: Granularity,
+    timezone: Tz,
+    periods: BTreeMap<NaiveDate, PeriodActivity>,
+}
+
+impl ActivityAnalyzer {
+    /// Creates a new `ActivityAnalyzer` bucketing by `granularity` in the given timezone.
+    pub fn new(granularity: Granularity, timezone: Tz) -> Self {
+        Self {
+            granularity,
+            timezone,
+            periods: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the first day of the period containing `date`.
+    fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self.granularity {
+            Granularity::Day => date,
+            Granularity::Week => date - Days::new(u64::from(date.weekday().num_days_from_monday())),
+            Granularity::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// Returns the first day of the period following the one starting at `start`.
+    fn next_period(&self, start: NaiveDate) -> Option<NaiveDate> {
+        match self.granularity {
+            Granularity::Day => start.checked_add_days(Days::new(1)),
+            Granularity::Week => start.checked_add_days(Days::new(7)),
+            Granularity::Month => start.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PeriodActivity {
+    commits: usize,
+    additions: usize,
+    deletions: usize,
+    authors: HashSet<String>,
+}
+
+impl CommitVisitor for ActivityAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+        let time = DateTime::from_timestamp(commit.time, 0).unwrap_or_default();
+        let start = self.period_start(time.with_timezone(&self.timezone).date_naive());
+
+        let entry = self.periods.entry(start).or_default();
+        entry.commits += 1;
+        entry.additions += churn.additions();
+        entry.deletions += churn.deletions();
+        entry.authors.insert(commit.author.clone());
+        Ok(())
+    }
+}
+
+impl Analyzer for ActivityAnalyzer {
+    type Output = ActivitySeries;
+
+    fn finish(
+        mut self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let mut periods = std::mem::take(&mut self.periods);
+
+        // Fill in quiet periods so that the series is continuous.
+        let mut buckets = Vec::with_capacity(periods.len());
+        let mut next = periods.keys().next().copied();
+        let last = periods.keys().next_back().copied();
+        while let (Some(start), Some(last)) = (next, last) {
+            if start > last {
+                break;
+            }
+            let activity = periods.remove(&start).unwrap_or_default();
+            buckets.push(ActivityBucket {
+                start,
+                commits: activity.commits,
+                additions: activity.additions,
+                deletions: activity.deletions,
+                active_authors: activity.authors.len(),
+            });
+            next = self.next_period(start);
+        }
+
+        Ok(ActivitySeries {
+            granularity: self.granularity,
+            timezone: self.timezone.name().to_string(),
+            buckets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_activity_buckets_in_timezone() {
+        let test_repo = TestRepo::new();
+        // 2023-11-14T22:13:20Z, a Tuesday; already Wednesday in Auckland.
+        let at = |days: i64| Time::new(1_700_000_000 + days * 24 * 60 * 60, 0);
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", at(0), "one");
+        test_repo.write("b.txt", "b\nb\n");
+        test_repo.commit_as("John Roe", "john@example.com", at(1), "two");
+        test_repo.write("c.txt", "c\n");
+        let end = test_repo
+            .commit_as("Jane Doe", "jane@example.com", at(15), "three")
+            .to_string();
+
+        let analyzer = ActivityAnalyzer::new(Granularity::Week, Tz::UTC);
+        let series = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, analyzer)
+            .unwrap();
+        let weeks: Vec<_> = series
+            .buckets
+            .iter()
+            .map(|b| (b.start.to_string(), b.commits, b.additions, b.active_authors))
+            .collect();
+        assert_eq!(
+            weeks,
+            [
+                ("2023-11-13".to_string(), 2, 3, 2),
+                ("2023-11-20".to_string(), 0, 0, 0),
+                ("2023-11-27".to_string(), 1, 1, 1),
+            ]
+        );
+
+        let analyzer = ActivityAnalyzer::new(Granularity::Day, Tz::Pacific__Auckland);
+        let series = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, analyzer)
+            .unwrap();
+        assert_eq!(series.timezone, "Pacific/Auckland");
+        assert_eq!(series.buckets[0].start.to_string(), "2023-11-15");
+        assert_eq!(series.buckets.len(), 16);
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+
+use crate::models::{AuthorShare, DirectoryKnowledge};
+use crate::repository::IdentityResolver;
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// The fraction of lines that must be lost for a directory to count as unowned.
+const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// Computes the bus factor of each directory, up to a configurable depth.
+pub struct BusFactorAnalyzer {
+    depth: usize,
+    threshold: f64,
+    root: DirectoryAccumulator,
+}
+
+impl BusFactorAnalyzer {
+    /// Creates a new `BusFactorAnalyzer` reporting directories up to `depth` levels deep.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            threshold: DEFAULT_THRESHOLD,
+            root: DirectoryAccumulator::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct DirectoryAccumulator {
+    authors: HashMap<String, usize>,
+    children: BTreeMap<String, DirectoryAccumulator>,
+}
+
+impl DirectoryAccumulator {
+    fn add(&mut self, components: &[String], author: &str, lines: usize) {
+        *self.authors.entry(author.to_string()).or_default() += lines;
+        if let Some((first, rest)) = components.split_first() {
+            self.children
+                .entry(first.clone())
+                .or_default()
+                .add(rest, author, lines);
+        }
+    }
+
+    fn into_knowledge(
+        self, path: String, threshold: f64, identities: &IdentityResolver,
+    ) -> DirectoryKnowledge {
+        let lines: usize = self.authors.values().sum();
+
+        let mut authors: Vec<_> = self
+            .authors
+            .into_iter()
+            .map(|(email, author_lines)| AuthorShare {
+                author: identities.name(&email).to_string(),
+                email,
+                lines: author_lines,
+                share: author_lines as f64 / lines.max(1) as f64,
+            })
+            .collect();
+        authors.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.author.cmp(&b.author)));
+
+        // Remove the largest owners first until most of the lines are gone.
+        let mut lost = 0;
+        let bus_factor = authors
+            .iter()
+            .take_while(|author| {
+                let done = lost as f64 > threshold * lines as f64;
+                lost += author.lines;
+                !done
+            })
+            .count();
+
+        let children = self
+            .children
+            .into_iter()
+            .map(|(name, child)| {
+                let child_path = if path.is_empty() {
+                    name
+                } else {
+                    format!("{}/{}", path, name)
+                };
+                child.into_knowledge(child_path, threshold, identities)
+            })
+            .collect();
+
+        DirectoryKnowledge {
+            path,
+            lines,
+            bus_factor,
+            authors,
+            children,
+        }
+    }
+}
+
+impl CommitVisitor for BusFactorAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+
+        for file in churn.files().iter().filter(|file| file.additions() > 0) {
+            let components: Vec<String> = Path::new(file.path())
+                .parent()
+                .into_iter()
+                .flat_map(|parent| parent.iter())
+                .take(self.depth)
+                .map(|component| component.to_string_lossy().into_owned())
+                .collect();
+            self.root.add(&components, &commit.author, file.additions());
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for BusFactorAnalyzer {
+    type Output = DirectoryKnowledge;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .root
+            .into_knowledge(String::new(), self.threshold, &context.identities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_bus_factor_per_directory() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial").to_string();
+
+        let time = Time::new(1_700_000_000, 0);
+        test_repo.write("core/src/lib.rs", "a\nb\nc\nd\ne\nf\n");
+        test_repo.write("api/main.rs", "a\nb\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "jane");
+        test_repo.write("api/routes.rs", "a\nb\nc\n");
+        test_repo.write("core/src/lib.rs", "a\nb\nc\nd\ne\nf\ng\n");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", time, "john")
+            .to_string();
+
+        let root = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, BusFactorAnalyzer::new(1))
+            .unwrap();
+
+        assert_eq!(root.path, "");
+        assert_eq!(root.lines, 12);
+        assert_eq!(root.bus_factor, 1);
+        assert_eq!(root.authors[0].author, "Jane Doe");
+
+        let paths: Vec<_> = root
+            .children
+            .iter()
+            .map(|child| child.path.as_str())
+            .collect();
+        assert_eq!(paths, ["api", "core"]);
+
+        let api = &root.children[0];
+        assert_eq!(api.lines, 5);
+        assert_eq!(api.bus_factor, 1);
+        assert_eq!(api.authors[0].author, "John Roe");
+
+        // Depth limits the tree, so `core/src` is folded into `core`.
+        let core = &root.children[1];
+        assert!(core.children.is_empty());
+        assert_eq!(core.lines, 7);
+    }
+}
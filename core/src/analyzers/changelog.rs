@@ -0,0 +1,321 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+use chrono::DateTime;
+
+use crate::commit::conventional::Commit;
+use crate::models::{
+    Changelog, ChangelogEntry, ChangelogScope, ChangelogSection, Repository as HostedRepository,
+};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// The section titles of the well-known commit types, in the order they are listed.
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance Improvements"),
+    ("revert", "Reverts"),
+    ("refactor", "Code Refactoring"),
+    ("docs", "Documentation"),
+    ("style", "Styles"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+    ("chore", "Chores"),
+];
+
+/// Groups the conventional commits of a range into changelog sections.
+///
+/// When only the first-parent chain is walked, merge commits whose own header is not
+/// conventional, like the ones of GitHub and GitLab merges, are listed by the conventional
+/// header in their body, if any. Otherwise the merged commits are walked and listed themselves,
+/// so the merges are left out rather than listing their changes twice.
+#[derive(Default)]
+pub struct ChangelogAnalyzer {
+    links: Option<HostedRepository>,
+    /// Commit types mapped to their entries by scope, in the order they are visited.
+    types: HashMap<String, BTreeMap<Option<String>, Vec<ChangelogEntry>>>,
+    breaking_changes: Vec<ChangelogEntry>,
+}
+
+impl ChangelogAnalyzer {
+    /// Links commits, pull requests and authors to the pages of the given hosted repository.
+    pub fn with_links(mut self, repository: HostedRepository) -> Self {
+        self.links = Some(repository);
+        self
+    }
+}
+
+/// Finds the number of the pull request that landed a commit in its message, as written by
+/// GitHub, GitLab, Bitbucket and Azure Repos.
+fn pull_request_number(message: &str) -> Option<u64> {
+    let leading_number = |text: &str| -> Option<u64> {
+        let end = text
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len());
+        text[..end].parse().ok()
+    };
+
+    // GitHub appends the pull request to the subject of squashed commits, as in `fix: x (#12)`.
+    let subject = message.lines().next().unwrap_or_default().trim_end();
+    if let Some(number) = subject
+        .strip_suffix(')')
+        .and_then(|subject| subject.rsplit_once(" (#"))
+        .and_then(|(_, number)| number.parse().ok())
+    {
+        return Some(number);
+    }
+    if let Some((_, reference)) = message.split_once("See merge request ") {
+        // GitLab references merge requests as `group/project!12`.
+        let (_, number) = reference.split_whitespace().next()?.rsplit_once('!')?;
+        return leading_number(number);
+    }
+    ["Merge pull request #", "(pull request #", "Merged PR "]
+        .iter()
+        .find_map(|marker| leading_number(message.split_once(marker)?.1))
+}
+
+impl CommitVisitor for ChangelogAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Commits
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let message = String::from_utf8_lossy(commit.commit.message_bytes());
+        let merge = commit.commit.parent_count() > 1 && context.filter.follows_first_parent();
+        let Some(conventional) = Commit::from_commit_message(&message, merge) else {
+            return Ok(());
+        };
+
+        let links = self.links.as_ref();
+        let id = commit.commit.id().to_string();
+        let email = commit.author.clone();
+        let pull_request = pull_request_number(&message);
+        let mut description = conventional.description().to_string();
+        if let Some(number) = pull_request {
+            // GitHub appends the pull request to the subject of squashed commits.
+            if let Some(stripped) = description.strip_suffix(&format!(" (#{})", number)) {
+                description = stripped.to_string();
+            }
+        }
+
+        let entry = ChangelogEntry {
+            short_commit: id[..7].to_string(),
+            commit_url: links.and_then(|links| links.commit_url(&id)),
+            commit: id,
+            commit_type: conventional.commit_type().to_lowercase(),
+            scope: conventional.scope().map(str::to_string),
+            description,
+            breaking: conventional.is_breaking(),
+            breaking_change: conventional.breaking_change().map(str::to_string),
+            author: context.identities.name(&email).to_string(),
+            author_url: links.and_then(|links| links.author_url(&email)),
+            email,
+            pull_request,
+            pull_request_url: pull_request
+                .and_then(|number| links.and_then(|links| links.pull_request_url(number))),
+        };
+        if entry.breaking {
+            self.breaking_changes.push(entry.clone());
+        }
+        self.types
+            .entry(entry.commit_type.clone())
+            .or_default()
+            .entry(entry.scope.clone())
+            .or_default()
+            .push(entry);
+        Ok(())
+    }
+}
+
+impl Analyzer for ChangelogAnalyzer {
+    type Output = Changelog;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let mut sections: Vec<ChangelogSection> = self
+            .types
+            .into_iter()
+            .map(|(commit_type, scopes)| ChangelogSection {
+                title: SECTIONS
+                    .iter()
+                    .find(|(known, _)| *known == commit_type)
+                    .map_or_else(|| commit_type.clone(), |(_, title)| title.to_string()),
+                commit_type,
+                scopes: scopes
+                    .into_iter()
+                    .map(|(scope, mut entries)| {
+                        entries.reverse();
+                        ChangelogScope { scope, entries }
+                    })
+                    .collect(),
+            })
+            .collect();
+        // Well-known types come first in their conventional order, followed by the others.
+        sections.sort_by_key(|section| {
+            let position = SECTIONS
+                .iter()
+                .position(|(known, _)| *known == section.commit_type);
+            (position.unwrap_or(SECTIONS.len()), section.commit_type.clone())
+        });
+
+        let date = DateTime::from_timestamp(context.end.time().seconds(), 0).unwrap_or_default();
+        let mut breaking_changes = self.breaking_changes;
+        breaking_changes.reverse();
+
+        Ok(Changelog {
+            version: None,
+            date: date.format("%Y-%m-%d").to_string(),
+            start_commit: context.start_commit.clone(),
+            end_commit: context.end_commit.clone(),
+            sections,
+            breaking_changes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::models::{Connection, GitHubRepository, HistoryMode};
+    use crate::repository::CommitFilter;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_pull_request_number() {
+        assert_eq!(pull_request_number("feat: add users endpoint (#12)"), Some(12));
+        assert_eq!(
+            pull_request_number("Merge pull request #15 from jane/docs\n\ndocs: x"),
+            Some(15)
+        );
+        assert_eq!(pull_request_number("Merged in docs (pull request #7)\n\ndocs: x"), Some(7));
+        assert_eq!(pull_request_number("Merged PR 42: docs: x"), Some(42));
+        assert_eq!(
+            pull_request_number("Merge branch 'docs' into 'main'\n\nSee merge request team/app!9"),
+            Some(9)
+        );
+        assert_eq!(pull_request_number("fix: handle `(#x)` in names"), None);
+    }
+
+    #[test]
+    fn test_changelog_groups_by_type_and_scope() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+
+        test_repo.write("docs/setup.md", "# Setup\n");
+        let docs = test_repo.commit("docs: describe setup");
+        test_repo.reset(start);
+        test_repo.write("src/api.rs", "fn users() {}\n");
+        test_repo.commit_as(
+            "Octo Cat",
+            "1+octocat@users.noreply.github.com",
+            Time::new(1_700_000_000, 0),
+            "feat(api): add users endpoint (#12)",
+        );
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        test_repo.commit("fix: handle empty body");
+        test_repo.write("README.md", "hello world\n");
+        test_repo.commit("update readme");
+        test_repo.write("src/api.rs", "fn users_v2() {}\n");
+        test_repo.commit("feat!: drop the v1 API\n\nBREAKING CHANGE: the v1 routes are gone");
+        test_repo.write("src/cli.rs", "fn cli() {}\n");
+        test_repo.commit("feat(cli): add a command line");
+        let end =
+            test_repo.merge(docs, "Merge pull request #15 from jane/docs\n\ndocs: describe setup");
+
+        let github = HostedRepository::GitHub(GitHubRepository {
+            owner: "acme".to_string(),
+            name: "app".to_string(),
+            connection: Connection::Https,
+        });
+        let analyzer = ChangelogAnalyzer::default().with_links(github);
+        let changelog = Pipeline::default()
+            .with_filter(CommitFilter::new(HistoryMode::FirstParent))
+            .analyze(test_repo.path(), &start.to_string(), &end.to_string(), analyzer)
+            .unwrap();
+
+        let titles: Vec<_> = changelog
+            .sections
+            .iter()
+            .map(|s| s.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Features", "Bug Fixes", "Documentation"]);
+
+        let scopes: Vec<_> = changelog.sections[0]
+            .scopes
+            .iter()
+            .map(|scope| scope.scope.as_deref())
+            .collect();
+        assert_eq!(scopes, [None, Some("api"), Some("cli")]);
+
+        let users = &changelog.sections[0].scopes[1].entries[0];
+        assert_eq!(users.description, "add users endpoint");
+        assert_eq!(users.pull_request, Some(12));
+        assert_eq!(users.pull_request_url.as_deref(), Some("https://github.com/acme/app/pull/12"));
+        assert_eq!(users.author_url.as_deref(), Some("https://github.com/octocat"));
+        assert_eq!(
+            users.commit_url,
+            Some(format!("https://github.com/acme/app/commit/{}", users.commit))
+        );
+
+        let setup = &changelog.sections[2].scopes[0].entries[0];
+        assert_eq!(setup.description, "describe setup");
+        assert_eq!(setup.pull_request, Some(15));
+
+        assert_eq!(changelog.breaking_changes.len(), 1);
+        assert_eq!(
+            changelog.breaking_changes[0].breaking_change.as_deref(),
+            Some("the v1 routes are gone")
+        );
+    }
+
+    #[test]
+    fn test_changelog_lists_merges_by_their_body_on_first_parent_history() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+        test_repo.write("docs/setup.md", "# Setup\n");
+        let docs = test_repo.commit("docs: describe setup");
+        test_repo.reset(start);
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        test_repo.commit("fix: handle empty body");
+        let merge =
+            test_repo.merge(docs, "Merge pull request #15 from jane/docs\n\ndocs: describe setup");
+
+        let documentation = |mode| {
+            let changelog = Pipeline::default()
+                .with_filter(CommitFilter::new(mode))
+                .analyze(
+                    test_repo.path(),
+                    &start.to_string(),
+                    &merge.to_string(),
+                    ChangelogAnalyzer::default(),
+                )
+                .unwrap();
+            let section = changelog
+                .sections
+                .into_iter()
+                .find(|section| section.title == "Documentation")
+                .unwrap();
+            section
+                .scopes
+                .into_iter()
+                .flat_map(|scope| scope.entries)
+                .map(|entry| (entry.commit, entry.pull_request))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(documentation(HistoryMode::FirstParent), [(merge.to_string(), Some(15))]);
+        // The merged commit is listed itself, so the merge would list it twice.
+        assert_eq!(documentation(HistoryMode::All), [(docs.to_string(), None)]);
+    }
+}
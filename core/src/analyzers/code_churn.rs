@@ -1,25 +1,35 @@
 use std::error::Error;
 
-use async_trait::async_trait;
-use git2::Repository;
+use crate::models::CodeChurn;
 
-use crate::{models::CodeChurn, repository::calculate_code_churn, repository::create_revwalk};
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
 
-use super::Analyzer;
+/// Collects the churn of each commit of a range, newest first.
+#[derive(Default)]
+pub struct CodeChurnAnalyzer {
+    churn_data: Vec<CodeChurn>,
+}
+
+impl CommitVisitor for CodeChurnAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
 
-pub struct CodeChurnAnalyzer;
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.churn_data.extend(commit.churn.clone());
+        Ok(())
+    }
+}
 
-#[async_trait]
 impl Analyzer for CodeChurnAnalyzer {
     type Output = Vec<CodeChurn>;
 
-    async fn analyze(
-        &self, repo_path: &str, old_commit: &str, new_commit: &str,
-    ) -> Result<Self::Output, Box<dyn Error>> {
-        let repo = Repository::open(&repo_path)?;
-        let revwalk = create_revwalk(&repo, &old_commit, &new_commit)?;
-        let churn_data = calculate_code_churn(&repo, revwalk)?;
-
-        Ok(churn_data)
+    fn finish(
+        mut self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        self.churn_data.reverse();
+        Ok(self.churn_data)
     }
 }
@@ -0,0 +1,240 @@
+use std::error::Error;
+
+use crate::commit::conventional::Commit;
+use crate::models::{
+    CommitViolations, Compliance, ComplianceRule, ComplianceRules, ComplianceViolation,
+};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Prefixes of the messages git and forges write themselves, which are not held to the rules.
+const GENERATED_MESSAGE_PREFIXES: &[&str] = &[
+    "Merge branch ",
+    "Merge pull request ",
+    "Revert \"",
+    "fixup! ",
+    "squash! ",
+    "amend! ",
+];
+
+/// Checks the commit messages of a range against the Conventional Commits specification and
+/// a set of rules on top of it.
+///
+/// Merge commits and messages generated by git are skipped. Path filters do not apply, as
+/// messages describe the whole commit.
+pub struct ComplianceAnalyzer {
+    rules: ComplianceRules,
+    checked: usize,
+    commits: Vec<CommitViolations>,
+}
+
+impl ComplianceAnalyzer {
+    /// Creates a new `ComplianceAnalyzer` checking messages against the given rules.
+    pub fn new(rules: ComplianceRules) -> Self {
+        Self {
+            rules,
+            checked: 0,
+            commits: Vec::new(),
+        }
+    }
+
+    /// Returns the rules broken by a commit message.
+    fn check(&self, message: &str) -> Vec<ComplianceViolation> {
+        let mut violations = Vec::new();
+        let mut violation = |rule, message| violations.push(ComplianceViolation { rule, message });
+
+        match message.parse::<Commit>() {
+            Ok(commit) => {
+                let allowed = &self.rules.allowed_types;
+                if !allowed.is_empty()
+                    && !allowed
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(commit.commit_type()))
+                {
+                    violation(
+                        ComplianceRule::Type,
+                        format!(
+                            "the type `{}` is not one of {}",
+                            commit.commit_type(),
+                            allowed.join(", ")
+                        ),
+                    );
+                }
+                if self.rules.require_scope && commit.scope().is_none() {
+                    violation(ComplianceRule::Scope, "the scope is missing".to_string());
+                }
+                if self.rules.require_breaking_change_footer
+                    && commit.has_breaking_marker()
+                    && commit.breaking_change().is_none()
+                {
+                    violation(
+                        ComplianceRule::BreakingChangeFooter,
+                        "the commit is marked as breaking but has no `BREAKING CHANGE` footer"
+                            .to_string(),
+                    );
+                }
+            }
+            Err(error) => violation(ComplianceRule::Format, error.to_string()),
+        }
+
+        let subject_length = message.lines().next().unwrap_or_default().chars().count();
+        if let Some(max) = self.rules.max_subject_length {
+            if subject_length > max {
+                violation(
+                    ComplianceRule::SubjectLength,
+                    format!(
+                        "the subject is {} characters long, at most {} are allowed",
+                        subject_length, max
+                    ),
+                );
+            }
+        }
+        violations
+    }
+}
+
+impl CommitVisitor for ComplianceAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Commits
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let message = String::from_utf8_lossy(commit.commit.message_bytes());
+        if commit.commit.parent_count() > 1
+            || GENERATED_MESSAGE_PREFIXES
+                .iter()
+                .any(|prefix| message.starts_with(prefix))
+        {
+            return Ok(());
+        }
+
+        self.checked += 1;
+        let violations = self.check(&message);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        self.commits.push(CommitViolations {
+            commit: commit.commit.id().to_string(),
+            author: context.identities.name(&commit.author).to_string(),
+            email: commit.author.clone(),
+            subject: message.lines().next().unwrap_or_default().to_string(),
+            violations,
+        });
+        Ok(())
+    }
+}
+
+impl Analyzer for ComplianceAnalyzer {
+    type Output = Compliance;
+
+    fn finish(
+        self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let checked = self.checked;
+        // Commits are visited oldest first.
+        let mut commits = self.commits;
+        commits.reverse();
+
+        let compliant = checked - commits.len();
+        Ok(Compliance {
+            checked,
+            compliant,
+            compliance_rate: if checked == 0 {
+                1.0
+            } else {
+                compliant as f64 / checked as f64
+            },
+            commits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzers::Pipeline;
+    use crate::annotations::prelude::{AnnotationLevel, AnnotationService, Platform};
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_check_rules() {
+        let analyzer = ComplianceAnalyzer::new(ComplianceRules {
+            require_scope: true,
+            max_subject_length: Some(32),
+            require_breaking_change_footer: true,
+            ..ComplianceRules::default()
+        });
+        let rules = |message| -> Vec<ComplianceRule> {
+            analyzer
+                .check(message)
+                .into_iter()
+                .map(|violation| violation.rule)
+                .collect()
+        };
+
+        assert_eq!(rules("fix(parser): handle empty input"), []);
+        assert_eq!(rules("Fix(parser): handle empty input"), []);
+        assert_eq!(rules("fixed the parser"), [ComplianceRule::Format]);
+        assert_eq!(rules("wip(parser): handle empty input"), [ComplianceRule::Type]);
+        assert_eq!(rules("fix: handle empty input"), [ComplianceRule::Scope]);
+        assert_eq!(
+            rules("fix(parser): handle empty input and whitespace"),
+            [ComplianceRule::SubjectLength]
+        );
+        assert_eq!(rules("feat(api)!: drop v1"), [ComplianceRule::BreakingChangeFooter]);
+        assert_eq!(rules("feat(api)!: drop v1\n\nBREAKING CHANGE: v1 is gone"), []);
+    }
+
+    #[test]
+    fn test_compliance_rate_and_annotations() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        test_repo.commit("feat: add entry point");
+        test_repo.write("src/main.rs", "fn main() {\n}\n");
+        let bad = test_repo.commit("formatted main");
+        test_repo.reset(start);
+        test_repo.write("docs/index.md", "# Docs\n");
+        test_repo.commit("docs: add index");
+        let merge = test_repo.merge(bad, "Merge the feature branch");
+        // A human subject that happens to start like a merge is still checked.
+        test_repo.write("src/sort.rs", "pub fn merge_sort() {}\n");
+        let sort = test_repo.commit("Merge sort helpers");
+        test_repo.write("src/sort.rs", "pub fn merge_sort() {}\npub fn quick_sort() {}\n");
+        test_repo.commit("fixup! Merge sort helpers");
+        let end = test_repo.commit("Merge branch 'main' into feature");
+
+        let analyzer = ComplianceAnalyzer::new(ComplianceRules::default());
+        let compliance = Pipeline::default()
+            .analyze(test_repo.path(), &start.to_string(), &end.to_string(), analyzer)
+            .unwrap();
+
+        assert_eq!(compliance.checked, 4);
+        assert_eq!(compliance.compliant, 2);
+        assert!((compliance.compliance_rate - 0.5).abs() < 1e-9);
+        let flagged: Vec<&str> = compliance
+            .commits
+            .iter()
+            .map(|commit| commit.commit.as_str())
+            .collect();
+        assert!(!flagged.contains(&merge.to_string().as_str()));
+        assert_eq!(flagged, [sort.to_string(), bad.to_string()]);
+        assert_eq!(compliance.commits[1].subject, "formatted main");
+
+        let service = AnnotationService::new_with_platform(Platform::GitHub).unwrap();
+        let annotations = compliance.annotations(AnnotationLevel::Error);
+        assert_eq!(
+            service.get_annotations_strings(&annotations[1..]),
+            [format!(
+                "::error ::{} \"formatted main\": expected '(', '!' or ':', found ' ' at line 1, \
+                 column 10",
+                &bad.to_string()[..7]
+            )]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+
+use crate::models::FileCoupling;
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Commits touching more files than this are ignored, as they are usually sweeping refactors or
+/// formatting changes that would couple every file with every other.
+const DEFAULT_MAX_CHANGESET_SIZE: usize = 50;
+
+/// Finds pairs of files that are frequently modified in the same commit.
+pub struct CouplingAnalyzer {
+    min_co_changes: usize,
+    max_changeset_size: usize,
+    total_commits: usize,
+    changes: HashMap<String, usize>,
+    co_changes: HashMap<(String, String), usize>,
+}
+
+impl CouplingAnalyzer {
+    /// Creates a new `CouplingAnalyzer` reporting pairs changed together at least
+    /// `min_co_changes` times.
+    pub fn new(min_co_changes: usize) -> Self {
+        Self {
+            min_co_changes,
+            max_changeset_size: DEFAULT_MAX_CHANGESET_SIZE,
+            total_commits: 0,
+            changes: HashMap::new(),
+            co_changes: HashMap::new(),
+        }
+    }
+}
+
+impl CommitVisitor for CouplingAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+        self.total_commits += 1;
+
+        let paths: BTreeSet<&str> = churn.files().iter().map(|file| file.path()).collect();
+        for path in &paths {
+            *self.changes.entry(path.to_string()).or_default() += 1;
+        }
+        if paths.len() > self.max_changeset_size {
+            return Ok(());
+        }
+
+        let paths: Vec<_> = paths.into_iter().collect();
+        for (idx, first) in paths.iter().enumerate() {
+            for second in &paths[idx + 1..] {
+                *self
+                    .co_changes
+                    .entry((first.to_string(), second.to_string()))
+                    .or_default() += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for CouplingAnalyzer {
+    type Output = Vec<FileCoupling>;
+
+    fn finish(
+        self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let changes = self.changes;
+        let total_commits = self.total_commits;
+        let mut couplings: Vec<_> = self
+            .co_changes
+            .into_iter()
+            .filter(|(_, count)| *count >= self.min_co_changes.max(1))
+            .map(|((first, second), count)| {
+                // Report the direction with the highest confidence: from the file that changes
+                // less often to the one that changes more often.
+                let (path, coupled_path) = if changes[&first] <= changes[&second] {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                FileCoupling {
+                    confidence: count as f64 / changes[&path] as f64,
+                    support: count as f64 / total_commits.max(1) as f64,
+                    co_changes: count,
+                    path,
+                    coupled_path,
+                }
+            })
+            .collect();
+
+        couplings.sort_by(|a, b| {
+            b.co_changes
+                .cmp(&a.co_changes)
+                .then(b.confidence.total_cmp(&a.confidence))
+                .then(a.path.cmp(&b.path))
+                .then(a.coupled_path.cmp(&b.coupled_path))
+        });
+
+        Ok(couplings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_coupling_support_and_confidence() {
+        let test_repo = TestRepo::new();
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        for n in 0..3 {
+            test_repo.write("api.rs", format!("{}\n", n));
+            test_repo.write("client.rs", format!("{}\n", n));
+            test_repo.commit("change api and client");
+        }
+        test_repo.write("api.rs", "solo\n");
+        test_repo.write("README.md", "changed once together\n");
+        let end = test_repo.commit("change api alone").to_string();
+
+        let couplings = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, CouplingAnalyzer::new(2))
+            .unwrap();
+
+        assert_eq!(couplings.len(), 1);
+        let coupling = &couplings[0];
+        assert_eq!(coupling.path, "client.rs");
+        assert_eq!(coupling.coupled_path, "api.rs");
+        assert_eq!(coupling.co_changes, 3);
+        assert_eq!(coupling.support, 0.75);
+        assert_eq!(coupling.confidence, 1.0);
+    }
+}
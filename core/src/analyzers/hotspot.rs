@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::Hotspot;
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Ranks files by change frequency and churn over a range of commits.
+#[derive(Default)]
+pub struct HotspotAnalyzer {
+    activity: HashMap<String, FileActivity>,
+}
+
+#[derive(Default)]
+struct FileActivity {
+    changes: usize,
+    additions: usize,
+    deletions: usize,
+    authors: HashSet<String>,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+impl CommitVisitor for HotspotAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+        let time = DateTime::from_timestamp(commit.time, 0).unwrap_or_default();
+
+        for file in churn.files() {
+            let entry = self.activity.entry(file.path().to_string()).or_default();
+            entry.changes += 1;
+            entry.additions += file.additions();
+            entry.deletions += file.deletions();
+            entry.authors.insert(commit.author.clone());
+            entry.last_modified = entry.last_modified.max(Some(time));
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for HotspotAnalyzer {
+    type Output = Vec<Hotspot>;
+
+    fn finish(
+        self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let mut hotspots: Vec<_> = self
+            .activity
+            .into_iter()
+            .map(|(path, activity)| Hotspot {
+                path,
+                changes: activity.changes,
+                additions: activity.additions,
+                deletions: activity.deletions,
+                churn: activity.additions + activity.deletions,
+                authors: activity.authors.len(),
+                last_modified: activity.last_modified.unwrap_or_default(),
+            })
+            .collect();
+
+        hotspots.sort_by(|a, b| {
+            b.changes
+                .cmp(&a.changes)
+                .then(b.churn.cmp(&a.churn))
+                .then(b.authors.cmp(&a.authors))
+                .then(b.last_modified.cmp(&a.last_modified))
+                .then(a.path.cmp(&b.path))
+        });
+
+        Ok(hotspots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_hotspots_are_ranked_by_change_frequency() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("src/lib.rs", "pub fn a() {}\n");
+        test_repo.write("README.md", "hello world\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", Time::new(1_700_000_000, 0), "one");
+        test_repo.write("src/lib.rs", "pub fn a() {}\npub fn b() {}\n");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", Time::new(1_700_086_400, 0), "two")
+            .to_string();
+
+        let hotspots = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, HotspotAnalyzer::default())
+            .unwrap();
+
+        assert_eq!(hotspots.len(), 2);
+        assert_eq!(hotspots[0].path, "src/lib.rs");
+        assert_eq!(hotspots[0].changes, 2);
+        assert_eq!(hotspots[0].churn, 2);
+        assert_eq!(hotspots[0].authors, 2);
+        assert_eq!(hotspots[0].last_modified.timestamp(), 1_700_086_400);
+        assert_eq!(hotspots[1].path, "README.md");
+        assert_eq!(hotspots[1].changes, 1);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use crate::models::LanguageBreakdown;
+use crate::repository::{GitAttributes, LanguageDetector};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// The language reported for files that could not be classified.
+const OTHER_LANGUAGE: &str = "Other";
+
+/// Breaks the churn of a range of commits down by programming language.
+///
+/// Languages are detected with the `.gitattributes` of the end commit, so that overrides made
+/// during the range apply to all of it.
+#[derive(Default)]
+pub struct LanguageAnalyzer {
+    /// Loaded with the attributes of the end commit when the first commit is visited.
+    detector: Option<LanguageDetector>,
+    activity: HashMap<String, LanguageActivity>,
+}
+
+#[derive(Default)]
+struct LanguageActivity {
+    commits: usize,
+    additions: usize,
+    deletions: usize,
+    files: HashSet<String>,
+    authors: HashSet<String>,
+}
+
+impl CommitVisitor for LanguageAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+        let detector = match &mut self.detector {
+            Some(detector) => detector,
+            None => self
+                .detector
+                .insert(LanguageDetector::new(GitAttributes::load(context.repo, &context.end)?)),
+        };
+        let tree = commit.commit.tree()?;
+        let mut languages = HashSet::new();
+
+        for file in churn.files() {
+            let language = detector
+                .detect(context.repo, &tree, file.path())
+                .unwrap_or(OTHER_LANGUAGE)
+                .to_string();
+            let entry = self.activity.entry(language.clone()).or_default();
+            entry.additions += file.additions();
+            entry.deletions += file.deletions();
+            entry.files.insert(file.path().to_string());
+            entry.authors.insert(commit.author.clone());
+            if languages.insert(language) {
+                entry.commits += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for LanguageAnalyzer {
+    type Output = Vec<LanguageBreakdown>;
+
+    fn finish(
+        self, _context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let mut languages: Vec<_> = self
+            .activity
+            .into_iter()
+            .map(|(language, activity)| LanguageBreakdown {
+                language,
+                commits: activity.commits,
+                additions: activity.additions,
+                deletions: activity.deletions,
+                churn: activity.additions + activity.deletions,
+                files: activity.files.len(),
+                authors: activity.authors.len(),
+            })
+            .collect();
+
+        languages.sort_by(|a, b| {
+            b.churn
+                .cmp(&a.churn)
+                .then(b.commits.cmp(&a.commits))
+                .then(a.language.cmp(&b.language))
+        });
+
+        Ok(languages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_languages_are_ranked_by_churn() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("src/main.rs", "fn main() {\n    run();\n}\n");
+        test_repo.write("web/app.ts", "export {};\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", Time::new(1_700_000_000, 0), "one");
+        test_repo.write("src/lib.rs", "pub fn run() {}\n");
+        test_repo.write("LICENSE", "MIT\n");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", Time::new(1_700_086_400, 0), "two")
+            .to_string();
+
+        let languages = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, LanguageAnalyzer::default())
+            .unwrap();
+
+        let names: Vec<_> = languages.iter().map(|l| l.language.as_str()).collect();
+        assert_eq!(names, ["Rust", "Other", "TypeScript"]);
+        assert_eq!(languages[0].commits, 2);
+        assert_eq!(languages[0].churn, 4);
+        assert_eq!(languages[0].files, 2);
+        assert_eq!(languages[0].authors, 2);
+    }
+}
@@ -1,17 +1,57 @@
 use std::error::Error;
 
-use async_trait::async_trait;
-
+pub use activity::ActivityAnalyzer;
+pub use bus_factor::BusFactorAnalyzer;
+pub use changelog::ChangelogAnalyzer;
 pub use code_churn::CodeChurnAnalyzer;
+pub use compliance::ComplianceAnalyzer;
+pub use coupling::CouplingAnalyzer;
+pub use hotspot::HotspotAnalyzer;
+pub use language::LanguageAnalyzer;
+pub use outlier::OutlierAnalyzer;
+pub use ownership::OwnershipAnalyzer;
+pub use pipeline::{AnalysisContext, Coverage, Pipeline, WalkedCommit};
+pub use rework::ReworkAnalyzer;
+pub use test_ratio::TestRatioAnalyzer;
 pub use top_contributor::TopContributorsAnalyzer;
+pub use work_pattern::WorkPatternAnalyzer;
 
+mod activity;
+mod bus_factor;
+mod changelog;
 mod code_churn;
+mod compliance;
+mod coupling;
+mod hotspot;
+mod language;
+mod outlier;
+mod ownership;
+mod pipeline;
+mod rework;
+mod test_ratio;
 mod top_contributor;
+mod work_pattern;
+
+/// Looks at the commits of a range as they are walked by a [`Pipeline`].
+pub trait CommitVisitor {
+    /// How much of the walked history the visitor looks at.
+    fn coverage(&self) -> Coverage;
 
-#[async_trait]
-pub trait Analyzer {
+    /// Visits a commit of the range, oldest first.
+    ///
+    /// Only the commits selected by the pipeline's filter are visited, unless the visitor covers
+    /// the whole history.
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Analyzes a range of commits from a walk of its history shared with other analyzers.
+pub trait Analyzer: CommitVisitor {
     type Output;
-    async fn analyze(
-        &self, repo_path: &str, old_commit: &str, new_commit: &str,
-    ) -> Result<Self::Output, Box<dyn Error>>;
+
+    /// Returns the result of the analysis once every commit of the range has been visited.
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>>;
 }
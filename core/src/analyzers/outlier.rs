@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use git2::Sort;
+
+use crate::models::{CodeChurn, FileStatus, OutlierCommit, OutlierMetric, OutlierReason, Outliers};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// How many of the most recent commits up to the end of a range make up its baseline.
+const BASELINE_COMMITS: usize = 200;
+
+/// The fewest commits a baseline needs for its medians to tell what is usual. With less history,
+/// only added binary files are flagged.
+const MIN_BASELINE_COMMITS: usize = 10;
+
+/// Turns a median absolute deviation into an estimate of the standard deviation, as it is for
+/// normally distributed values.
+const MAD_SCALE: f64 = 1.4826;
+
+/// Turns a mean absolute deviation into an estimate of the standard deviation, for when most
+/// values are equal and the median absolute deviation is zero.
+const MEAN_AD_SCALE: f64 = 1.2533;
+
+/// Flags the commits of a range that are statistically unusual: huge diffs, mass deletions,
+/// many files touched, or binary files added.
+///
+/// Each commit is scored against the median and median absolute deviation of a baseline, the
+/// most recent commits of the history up to the end of the range, so that a short range is
+/// measured against what is usual for the repository rather than against itself. Scores are
+/// taken on a logarithmic scale as commit sizes are heavily skewed. Merge commits are left out
+/// unless the history follows first parents, as their diffs repeat the merged branch.
+pub struct OutlierAnalyzer {
+    threshold: f64,
+    commits: Vec<MeasuredCommit>,
+}
+
+impl OutlierAnalyzer {
+    /// Creates a new `OutlierAnalyzer` flagging commits whose modified z-score exceeds the
+    /// given threshold.
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            commits: Vec::new(),
+        }
+    }
+}
+
+struct MeasuredCommit {
+    commit: String,
+    email: String,
+    subject: String,
+    size: Size,
+    binaries: Vec<String>,
+}
+
+/// The size of a commit's changes to the selected files.
+#[derive(Clone, Copy)]
+struct Size {
+    additions: usize,
+    deletions: usize,
+    files: usize,
+}
+
+impl Size {
+    fn of(churn: &CodeChurn) -> Self {
+        Self {
+            additions: churn.additions(),
+            deletions: churn.deletions(),
+            files: churn.files().len(),
+        }
+    }
+
+    fn changed(&self) -> usize {
+        self.additions + self.deletions
+    }
+}
+
+/// What is usual for a metric over the commits of a baseline.
+struct Baseline {
+    /// The median of the raw values, for reporting.
+    median: f64,
+    /// The median of the values on a logarithmic scale.
+    log_median: f64,
+    /// The robust estimate of the standard deviation on a logarithmic scale.
+    log_deviation: f64,
+}
+
+impl Baseline {
+    fn new(values: impl Iterator<Item = usize>) -> Self {
+        let values: Vec<f64> = values.map(|value| value as f64).collect();
+        let logs: Vec<f64> = values.iter().map(|value| value.ln_1p()).collect();
+        let log_median = median(logs.clone());
+        let deviations: Vec<f64> = logs
+            .iter()
+            .map(|value| (value - log_median).abs())
+            .collect();
+        let mad = median(deviations.clone());
+        let log_deviation = if mad > 0.0 {
+            MAD_SCALE * mad
+        } else {
+            MEAN_AD_SCALE * deviations.iter().sum::<f64>() / deviations.len().max(1) as f64
+        };
+        Self {
+            median: median(values),
+            log_median,
+            log_deviation,
+        }
+    }
+
+    /// Returns the modified z-score of a value, or `None` when every value is the same.
+    fn score(&self, value: usize) -> Option<f64> {
+        (self.log_deviation > 0.0)
+            .then(|| ((value as f64).ln_1p() - self.log_median) / self.log_deviation)
+    }
+}
+
+/// Returns the median of the given values, or 0 when there are none.
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+impl CommitVisitor for OutlierAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
+        if churn.files().is_empty()
+            || commit.commit.parent_count() > 1 && !context.filter.follows_first_parent()
+        {
+            return Ok(());
+        }
+
+        let message = String::from_utf8_lossy(commit.commit.message_bytes());
+        self.commits.push(MeasuredCommit {
+            commit: commit.commit.id().to_string(),
+            email: commit.author.clone(),
+            subject: message.lines().next().unwrap_or_default().to_string(),
+            size: Size::of(churn),
+            binaries: churn
+                .files()
+                .iter()
+                .filter(|file| file.is_binary() && file.status() == FileStatus::Added)
+                .map(|file| file.path().to_string())
+                .collect(),
+        });
+        Ok(())
+    }
+}
+
+/// Measures the most recent commits of the history up to the end of the range, reusing the
+/// commits of the range that were measured already.
+///
+/// The other commits are diffed like the walk diffs them, in parallel and through its cache,
+/// a batch of as many commits as the baseline still lacks at a time.
+fn baseline_sizes(
+    context: &AnalysisContext, measured: &[MeasuredCommit],
+) -> Result<Vec<Size>, Box<dyn Error + Send + Sync>> {
+    let measured: HashMap<&str, Size> = measured
+        .iter()
+        .map(|commit| (commit.commit.as_str(), commit.size))
+        .collect();
+    let mut revwalk = context.repo.revwalk()?;
+    revwalk.push(context.end.id())?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL)?;
+    let mut history = context.filter.commits(context.repo, revwalk)?;
+
+    let mut sizes = Vec::new();
+    let mut exhausted = false;
+    while sizes.len() < BASELINE_COMMITS && !exhausted {
+        // The measured size of each commit of the batch, or `None` for the ones to diff.
+        let mut batch = Vec::new();
+        let mut unmeasured = Vec::new();
+        while batch.len() < BASELINE_COMMITS - sizes.len() {
+            let Some(id) = history.next() else {
+                exhausted = true;
+                break;
+            };
+            let id = id?;
+            if let Some(size) = measured.get(id.to_string().as_str()) {
+                batch.push(Some(*size));
+            } else if context.repo.find_commit(id)?.parent_count() < 2
+                || context.filter.follows_first_parent()
+            {
+                batch.push(None);
+                unmeasured.push(id);
+            }
+        }
+
+        let mut diffs = context.diffs(&unmeasured)?.into_iter();
+        for size in batch {
+            let size = size.or_else(|| {
+                let mut churn = diffs.next()?.churn;
+                churn.retain_files(|file| context.paths.includes(file.path()));
+                (!churn.files().is_empty()).then(|| Size::of(&churn))
+            });
+            sizes.extend(size);
+        }
+    }
+    Ok(sizes)
+}
+
+impl Analyzer for OutlierAnalyzer {
+    type Output = Outliers;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let sizes = baseline_sizes(context, &self.commits)?;
+        let baselines = (sizes.len() >= MIN_BASELINE_COMMITS).then(|| {
+            let baseline = |metric: fn(&Size) -> usize| Baseline::new(sizes.iter().map(metric));
+            (
+                baseline(Size::changed),
+                baseline(|size| size.deletions),
+                baseline(|size| size.files),
+            )
+        });
+
+        let threshold = self.threshold;
+        let reason = |metric, baseline: &Baseline, value, message: String| {
+            let score = baseline.score(value).filter(|score| *score > threshold)?;
+            Some(OutlierReason {
+                metric,
+                value,
+                median: Some(baseline.median),
+                score: Some(score),
+                message: format!("{}, against a median of {}", message, baseline.median),
+            })
+        };
+
+        // Commits are visited oldest first.
+        let mut commits = Vec::new();
+        for commit in self.commits.iter().rev() {
+            let mut reasons = Vec::new();
+            let size = commit.size;
+            if let Some((churn, deletions, files)) = &baselines {
+                let changed = size.changed();
+                reasons.extend(reason(
+                    OutlierMetric::Churn,
+                    churn,
+                    changed,
+                    format!("changed {} lines", changed),
+                ));
+                if size.deletions > size.additions {
+                    reasons.extend(reason(
+                        OutlierMetric::Deletions,
+                        deletions,
+                        size.deletions,
+                        format!("deleted {} lines", size.deletions),
+                    ));
+                }
+                reasons.extend(reason(
+                    OutlierMetric::Files,
+                    files,
+                    size.files,
+                    format!("touched {} files", size.files),
+                ));
+            }
+            if !commit.binaries.is_empty() {
+                reasons.push(OutlierReason {
+                    metric: OutlierMetric::BinaryFiles,
+                    value: commit.binaries.len(),
+                    median: None,
+                    score: None,
+                    message: format!("added binary files {}", commit.binaries.join(", ")),
+                });
+            }
+            if reasons.is_empty() {
+                continue;
+            }
+
+            commits.push(OutlierCommit {
+                commit: commit.commit.clone(),
+                author: context.identities.name(&commit.email).to_string(),
+                email: commit.email.clone(),
+                subject: commit.subject.clone(),
+                additions: size.additions,
+                deletions: size.deletions,
+                files: size.files,
+                reasons,
+            });
+        }
+
+        Ok(Outliers {
+            measured: self.commits.len(),
+            baseline: sizes.len(),
+            threshold,
+            commits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzers::Pipeline;
+    use crate::annotations::prelude::{AnnotationLevel, AnnotationService, Platform};
+    use crate::repository::CommitCache;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_flag_outlier_commits() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+
+        for n in 1..=12 {
+            test_repo.write(&format!("src/module_{}.rs", n), "fn f() {}\n".repeat(n % 3 + 1));
+            test_repo.commit(&format!("feat: add module {}", n));
+        }
+        let generated = (0..40)
+            .map(|n| format!("pub const VALUE_{}: u32 = {};\n", n, n))
+            .collect::<String>();
+        test_repo.write("src/generated.rs", generated.repeat(10));
+        let huge = test_repo.commit("feat: add generated constants");
+        test_repo.remove("src/generated.rs");
+        let deletion = test_repo.commit("chore: drop generated constants");
+        test_repo.write("assets/logo.png", b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".as_slice());
+        let end = test_repo.commit("docs: add logo");
+
+        let outliers = Pipeline::default()
+            .analyze(
+                test_repo.path(),
+                &start.to_string(),
+                &end.to_string(),
+                OutlierAnalyzer::new(3.5),
+            )
+            .unwrap();
+
+        assert_eq!(outliers.measured, 15);
+        // The baseline reaches back to the initial commit.
+        assert_eq!(outliers.baseline, 16);
+        let flagged: Vec<(String, Vec<OutlierMetric>)> = outliers
+            .commits
+            .iter()
+            .map(|commit| {
+                let metrics = commit.reasons.iter().map(|reason| reason.metric).collect();
+                (commit.commit.clone(), metrics)
+            })
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                (end.to_string(), vec![OutlierMetric::BinaryFiles]),
+                (deletion.to_string(), vec![OutlierMetric::Churn, OutlierMetric::Deletions]),
+                (huge.to_string(), vec![OutlierMetric::Churn]),
+            ]
+        );
+        let churn = &outliers.commits[2].reasons[0];
+        assert_eq!(churn.value, 400);
+        assert_eq!(churn.median, Some(2.0));
+        assert_eq!(churn.message, "changed 400 lines, against a median of 2");
+
+        let service = AnnotationService::new_with_platform(Platform::GitHub).unwrap();
+        let annotations = outliers.annotations(AnnotationLevel::Warning);
+        assert_eq!(annotations.len(), 4);
+        assert_eq!(
+            service.get_annotations_strings(&annotations[..1]),
+            [format!(
+                "::warning ::{} \"docs: add logo\": added binary files assets/logo.png",
+                &end.to_string()[..7]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_measure_short_range_against_history() {
+        let test_repo = TestRepo::new();
+        let history: Vec<_> = (1..=20)
+            .map(|n| {
+                test_repo.write(&format!("src/module_{}.rs", n), "fn f() {}\n".repeat(n % 3 + 1));
+                test_repo.commit(&format!("feat: add module {}", n))
+            })
+            .collect();
+        let start = history[history.len() - 1];
+        test_repo.write("src/small.rs", "fn g() {}\n");
+        test_repo.commit("feat: add small module");
+        test_repo.write("src/generated.rs", "pub const VALUE: u32 = 0;\n".repeat(500));
+        let huge = test_repo.commit("feat: add generated constants");
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CommitCache::open(dir.path().join("commits.redb")).unwrap();
+        let outliers = Pipeline::default()
+            .with_cache(cache.clone())
+            .analyze(
+                test_repo.path(),
+                &start.to_string(),
+                &huge.to_string(),
+                OutlierAnalyzer::new(3.5),
+            )
+            .unwrap();
+
+        assert_eq!(outliers.measured, 2);
+        assert_eq!(outliers.baseline, 22);
+        assert_eq!(outliers.commits.len(), 1);
+        assert_eq!(outliers.commits[0].commit, huge.to_string());
+        assert_eq!(outliers.commits[0].reasons[0].metric, OutlierMetric::Churn);
+        assert_eq!(outliers.commits[0].reasons[0].median, Some(2.0));
+        // The history before the range is diffed through the cache like the range itself.
+        assert!(cache.get(&history).unwrap().iter().all(Option::is_some));
+    }
+}
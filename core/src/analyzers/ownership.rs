@@ -0,0 +1,433 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use cached::{Cached, SizedCache};
+use git2::{
+    Blame, BlameOptions, Commit, ObjectType, Oid, Repository, Signature, TreeWalkMode,
+    TreeWalkResult,
+};
+use rayon::prelude::*;
+
+use crate::models::{AuthorShare, FileOwnership, Ownership};
+use crate::repository::IdentityResolver;
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// The file listing revisions to skip when blaming, as used by `git blame`.
+const DEFAULT_IGNORE_REVS_FILE: &str = ".git-blame-ignore-revs";
+
+/// How far to follow a line through ignored revisions before giving up.
+const MAX_IGNORED_DEPTH: usize = 16;
+
+/// How many blamed files are kept in memory.
+const BLAME_CACHE_SIZE: usize = 65536;
+
+/// The surviving lines per author of blamed files, by repository, path, blob and set of ignored
+/// revisions.
+///
+/// Blames are keyed on the content of the file rather than on the end commit, so that a file
+/// left unchanged by later commits is not blamed again when a later end commit is analyzed. Its
+/// lines are credited as they were blamed from the first end commit that had this content.
+static BLAME_CACHE: OnceLock<Mutex<SizedCache<BlameKey, HashMap<BlameAuthor, usize>>>> =
+    OnceLock::new();
+
+/// The repository, path, blob id and hash of the ignored revisions of a blamed file.
+type BlameKey = (PathBuf, String, Oid, u64);
+
+/// Attributes the surviving lines of every tracked file at the end commit to their authors.
+///
+/// Only the end commit matters: lines are credited to whoever last touched them, regardless of
+/// whether that happened inside the analyzed range. Lines last touched by bots excluded by the
+/// pipeline's filter are left unowned, and the files it excludes are skipped, but its history
+/// mode does not apply.
+#[derive(Default)]
+pub struct OwnershipAnalyzer;
+
+/// The raw name and email of a blamed author, before identity resolution.
+type BlameAuthor = (String, String);
+
+impl CommitVisitor for OwnershipAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::End
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, _commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+impl Analyzer for OwnershipAnalyzer {
+    type Output = Ownership;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let repo = context.repo;
+        let commit = &context.end;
+        let ignored = ignored_revisions(repo, commit)?;
+        let ignored_key = ignored_key(&ignored);
+        let mut blobs = tracked_blobs(repo, commit)?;
+        blobs.retain(|(path, _)| context.paths.includes(path));
+        let commit_id = commit.id();
+        let repo_path = repo.path();
+
+        let keys: Vec<BlameKey> = blobs
+            .into_iter()
+            .map(|(path, blob_id)| (repo_path.to_path_buf(), path, blob_id, ignored_key))
+            .collect();
+        let mut results: Vec<Option<HashMap<BlameAuthor, usize>>> = {
+            let mut cache = blame_cache();
+            keys.iter()
+                .map(|key| cache.cache_get(key).cloned())
+                .collect()
+        };
+        let missing: Vec<&BlameKey> = keys
+            .iter()
+            .zip(&results)
+            .filter(|(_, lines)| lines.is_none())
+            .map(|(key, _)| key)
+            .collect();
+        let blamed: Vec<HashMap<BlameAuthor, usize>> = missing
+            .par_iter()
+            .map_init(
+                || Repository::open(repo_path),
+                |worker_repo, (_, path, _, _)| {
+                    let worker_repo = worker_repo.as_ref().map_err(|e| {
+                        git2::Error::from_str(&format!("Failed to open repository: {}", e))
+                    })?;
+                    blame_blob(worker_repo, Path::new(path), commit_id, &ignored)
+                },
+            )
+            .collect::<Result<_, git2::Error>>()?;
+
+        {
+            let mut cache = blame_cache();
+            for (key, lines) in missing.into_iter().zip(&blamed) {
+                cache.cache_set(key.clone(), lines.clone());
+            }
+        }
+        let mut blamed = blamed.into_iter();
+        for lines in results.iter_mut().filter(|lines| lines.is_none()) {
+            *lines = blamed.next();
+        }
+        let results = keys
+            .into_iter()
+            .zip(results)
+            .filter_map(|((_, path, _, _), lines)| Some((path, lines?)));
+
+        let mut totals: HashMap<String, usize> = HashMap::new();
+        let mut files = Vec::new();
+        for (path, blamed) in results {
+            let mut lines: HashMap<String, usize> = HashMap::new();
+            for ((name, email), count) in blamed {
+                if context
+                    .filter
+                    .bots()
+                    .is_some_and(|bots| bots.is_bot(&name, &email))
+                {
+                    continue;
+                }
+                let email = context.identities.resolve_parts(&name, &email);
+                *totals.entry(email.clone()).or_default() += count;
+                *lines.entry(email).or_default() += count;
+            }
+            let (lines, authors) = author_shares(lines, &context.identities);
+            files.push(FileOwnership {
+                path,
+                lines,
+                authors,
+            });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let (lines, authors) = author_shares(totals, &context.identities);
+        Ok(Ownership {
+            commit: commit_id.to_string(),
+            lines,
+            authors,
+            files,
+        })
+    }
+}
+
+/// Converts per-author line counts into shares, largest owner first.
+fn author_shares(
+    lines: HashMap<String, usize>, identities: &IdentityResolver,
+) -> (usize, Vec<AuthorShare>) {
+    let total: usize = lines.values().sum();
+    let mut authors: Vec<_> = lines
+        .into_iter()
+        .map(|(email, author_lines)| AuthorShare {
+            author: identities.name(&email).to_string(),
+            email,
+            lines: author_lines,
+            share: author_lines as f64 / total.max(1) as f64,
+        })
+        .collect();
+    authors.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.author.cmp(&b.author)));
+    (total, authors)
+}
+
+/// Lists every tracked, non-binary file in the commit's tree together with its blob id.
+fn tracked_blobs(repo: &Repository, commit: &Commit) -> Result<Vec<(String, Oid)>, git2::Error> {
+    let mut blobs = Vec::new();
+    commit.tree()?.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            if let Some(name) = entry.name() {
+                blobs.push((format!("{}{}", root, name), entry.id()));
+            }
+        }
+        TreeWalkResult::Ok
+    })?;
+
+    let mut text_blobs = Vec::with_capacity(blobs.len());
+    for (path, id) in blobs {
+        if !repo.find_blob(id)?.is_binary() {
+            text_blobs.push((path, id));
+        }
+    }
+    Ok(text_blobs)
+}
+
+/// Reads the revisions to ignore from `blame.ignoreRevsFile` or `.git-blame-ignore-revs`.
+///
+/// A relative path is looked up in the commit's tree, and an absolute one on disk.
+fn ignored_revisions(repo: &Repository, commit: &Commit) -> Result<HashSet<Oid>, git2::Error> {
+    let ignore_file = repo
+        .config()
+        .and_then(|config| config.get_string("blame.ignoreRevsFile"))
+        .unwrap_or_else(|_| DEFAULT_IGNORE_REVS_FILE.to_string());
+
+    let content = if Path::new(&ignore_file).is_absolute() {
+        fs::read(&ignore_file)
+            .map_err(|e| git2::Error::from_str(&format!("Failed to read {}: {}", ignore_file, e)))?
+    } else {
+        match commit.tree()?.get_path(Path::new(&ignore_file)) {
+            Ok(entry) => repo.find_blob(entry.id())?.content().to_vec(),
+            Err(_) => return Ok(HashSet::new()),
+        }
+    };
+
+    Ok(String::from_utf8_lossy(&content)
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .filter_map(|rev| repo.revparse_single(rev).ok())
+        .map(|object| object.id())
+        .collect())
+}
+
+/// Hashes a set of ignored revisions regardless of its iteration order.
+fn ignored_key(ignored: &HashSet<Oid>) -> u64 {
+    let mut revisions: Vec<&Oid> = ignored.iter().collect();
+    revisions.sort_unstable();
+    let mut hasher = DefaultHasher::new();
+    revisions.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Locks the cache of blamed files, which stays usable if a worker panicked while holding it.
+fn blame_cache() -> std::sync::MutexGuard<'static, SizedCache<BlameKey, HashMap<BlameAuthor, usize>>>
+{
+    BLAME_CACHE
+        .get_or_init(|| Mutex::new(SizedCache::with_size(BLAME_CACHE_SIZE)))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Blames a file at the given commit and counts the surviving lines per author.
+fn blame_blob(
+    repo: &Repository, path: &Path, newest_commit: Oid, ignored: &HashSet<Oid>,
+) -> Result<HashMap<BlameAuthor, usize>, git2::Error> {
+    let blame = blame_at(repo, path, newest_commit)?;
+    let mut previous_blames = HashMap::new();
+    let mut lines = HashMap::new();
+
+    for hunk in blame.iter() {
+        if !ignored.contains(&hunk.final_commit_id()) {
+            let author = blame_author(&hunk.final_signature());
+            *lines.entry(author).or_default() += hunk.lines_in_hunk();
+            continue;
+        }
+
+        let hunk_path = hunk.path().unwrap_or(path).to_path_buf();
+        for offset in 0..hunk.lines_in_hunk() {
+            let author = attribute_ignored_line(
+                repo,
+                &hunk_path,
+                hunk.final_commit_id(),
+                hunk.orig_start_line() + offset,
+                ignored,
+                &mut previous_blames,
+            )
+            .unwrap_or_else(|| blame_author(&hunk.final_signature()));
+            *lines.entry(author).or_default() += 1;
+        }
+    }
+
+    Ok(lines)
+}
+
+fn blame_author(signature: &Signature) -> BlameAuthor {
+    (
+        signature.name().unwrap_or("Unknown").to_string(),
+        signature.email().unwrap_or_default().to_string(),
+    )
+}
+
+fn blame_at<'repo>(
+    repo: &'repo Repository, path: &Path, newest_commit: Oid,
+) -> Result<Blame<'repo>, git2::Error> {
+    let mut options = BlameOptions::new();
+    options.newest_commit(newest_commit);
+    repo.blame_file(path, Some(&mut options))
+}
+
+/// Follows a line introduced by an ignored revision back to the author who touched it before.
+///
+/// Like `git blame --ignore-rev`, this is a best effort: the line is looked up at the same
+/// position in the parent revision. Returns `None` when no earlier author can be found.
+fn attribute_ignored_line<'repo>(
+    repo: &'repo Repository, path: &Path, mut commit_id: Oid, mut line: usize,
+    ignored: &HashSet<Oid>, previous_blames: &mut HashMap<Oid, Option<Blame<'repo>>>,
+) -> Option<BlameAuthor> {
+    for _ in 0..MAX_IGNORED_DEPTH {
+        let parent_id = repo.find_commit(commit_id).ok()?.parent_id(0).ok()?;
+        let blame = previous_blames
+            .entry(parent_id)
+            .or_insert_with(|| blame_at(repo, path, parent_id).ok())
+            .as_ref()?;
+        let hunk = blame.get_line(line)?;
+
+        if !ignored.contains(&hunk.final_commit_id()) {
+            return Some(blame_author(&hunk.final_signature()));
+        }
+        line = hunk.orig_start_line() + (line - hunk.final_start_line());
+        commit_id = hunk.final_commit_id();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_ownership_honours_ignore_revs() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("src/lib.rs", "a\nb\nc\nd\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "jane");
+        test_repo.write("src/lib.rs", "a\nb\nc\nd\ne\n");
+        test_repo.write("README.md", "hello\n");
+        test_repo.commit_as("John Roe", "john@example.com", time, "john");
+
+        // A formatting commit that rewrites every line but should not take ownership.
+        test_repo.write("src/lib.rs", "A\nB\nC\nD\nE\n");
+        let format = test_repo.commit_as("Bot", "bot@example.com", time, "format");
+        test_repo.write(".git-blame-ignore-revs", format!("# formatting\n{}\n", format));
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", time, "ignore formatting")
+            .to_string();
+
+        let ownership = Pipeline::default()
+            .analyze(test_repo.path(), &end, &end, OwnershipAnalyzer)
+            .unwrap();
+
+        let lib = ownership
+            .files
+            .iter()
+            .find(|file| file.path == "src/lib.rs")
+            .unwrap();
+        assert_eq!(lib.lines, 5);
+        assert_eq!(lib.authors[0].author, "Jane Doe");
+        assert_eq!(lib.authors[0].lines, 4);
+        assert_eq!(lib.authors[1].author, "John Roe");
+        assert_eq!(lib.authors[1].lines, 1);
+
+        assert_eq!(ownership.lines, 8);
+        assert!(ownership
+            .authors
+            .iter()
+            .all(|author| author.author != "Bot"));
+    }
+
+    #[test]
+    fn test_ownership_reuses_blame_of_unchanged_files() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("src/lib.rs", "a\nb\n");
+        let first = test_repo
+            .commit_as("Jane Doe", "jane@example.com", time, "jane")
+            .to_string();
+        test_repo.write("README.md", "hello\n");
+        let second = test_repo
+            .commit_as("John Roe", "john@example.com", time, "john")
+            .to_string();
+        for end in [&first, &second] {
+            let ownership = Pipeline::default()
+                .analyze(test_repo.path(), end, end, OwnershipAnalyzer)
+                .unwrap();
+            let lib = ownership
+                .files
+                .iter()
+                .find(|file| file.path == "src/lib.rs")
+                .unwrap();
+            assert_eq!(lib.authors[0].author, "Jane Doe");
+        }
+
+        // The file is unchanged between the two end commits, so it is only blamed once.
+        let repo_path = test_repo.repo.path().to_path_buf();
+        let blamed = blame_cache()
+            .key_order()
+            .filter(|(repo, path, _, _)| *repo == repo_path && path == "src/lib.rs")
+            .count();
+        assert_eq!(blamed, 1);
+    }
+
+    #[test]
+    fn test_ownership_rereads_changed_ignore_revs() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("src/lib.rs", "a\nb\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "jane");
+        test_repo.write("src/lib.rs", "A\nB\n");
+        let format = test_repo.commit_as("Bot", "bot@example.com", time, "format");
+        let end = format.to_string();
+        let owner = || {
+            let ownership = Pipeline::default()
+                .analyze(test_repo.path(), &end, &end, OwnershipAnalyzer)
+                .unwrap();
+            ownership.authors[0].author.clone()
+        };
+        assert_eq!(owner(), "Bot");
+
+        // Ignoring the formatting commit from outside the repository must not hit the blame
+        // cached without it.
+        let dir = tempfile::tempdir().unwrap();
+        let ignore_file = dir.path().join("ignore-revs");
+        fs::write(&ignore_file, format!("{}\n", format)).unwrap();
+        test_repo
+            .repo
+            .config()
+            .unwrap()
+            .set_str("blame.ignoreRevsFile", ignore_file.to_str().unwrap())
+            .unwrap();
+        assert_eq!(owner(), "Jane Doe");
+    }
+}
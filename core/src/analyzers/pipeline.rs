@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use git2::{Commit, Oid, Repository};
+use rayon::prelude::*;
+
+use crate::models::CodeChurn;
+use crate::repository::{
+    CachedCommit, CommitCache, CommitDiff, CommitFilter, CommitRange, IdentityResolver, PathMatcher,
+};
+
+use super::CommitVisitor;
+
+/// How many commits are looked up and diffed at once, which bounds the diffs held in memory.
+const BATCH_SIZE: usize = 512;
+
+/// How much of the walked history a visitor looks at, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Coverage {
+    /// Only the end of the range, so no commit is visited.
+    End,
+    /// The commits selected by the filter, without their diffs.
+    Commits,
+    /// The commits selected by the filter and their diffs.
+    Diffs,
+    /// Every walked commit and its diff, including the ones the filter leaves out.
+    History,
+}
+
+/// The range of a walk, shared by the analyzers it feeds.
+pub struct AnalysisContext<'r> {
+    pub repo: &'r Repository,
+    /// The revision the range starts from, exclusive, as given.
+    pub start_commit: String,
+    /// The revision the range ends at, inclusive, as given.
+    pub end_commit: String,
+    /// The commit at the end of the range.
+    pub end: Commit<'r>,
+    /// The commits of the range, newest first.
+    pub commits: &'r [Oid],
+    pub filter: &'r CommitFilter,
+    /// The files selected by the filter, resolved at the end of the range.
+    pub paths: PathMatcher<'r>,
+    /// Resolves authors to canonical emails, and those emails to display names.
+    pub identities: IdentityResolver,
+    cache: Option<&'r CommitCache>,
+}
+
+impl AnalysisContext<'_> {
+    /// Diffs the given commits the way the walk does, in parallel and through its cache, so that
+    /// analyzers looking past the range do not diff commits again.
+    ///
+    /// The diffs are returned in the order of the given commits.
+    pub fn diffs(&self, ids: &[Oid]) -> Result<Vec<CommitDiff>, Box<dyn Error + Send + Sync>> {
+        Ok(self
+            .records(ids)?
+            .into_iter()
+            .map(|record| record.diff)
+            .collect())
+    }
+
+    /// Records the given commits, in the same order, reading the ones it has from the cache.
+    fn records(&self, ids: &[Oid]) -> Result<Vec<CachedCommit>, Box<dyn Error + Send + Sync>> {
+        let Some(cache) = self.cache else {
+            return Ok(record_commits(self.repo, ids)?);
+        };
+        let mut records = cache.get(ids)?;
+        let missing: Vec<Oid> = ids
+            .iter()
+            .zip(&records)
+            .filter(|(_, record)| record.is_none())
+            .map(|(id, _)| *id)
+            .collect();
+        let computed = record_commits(self.repo, &missing)?;
+        cache.insert(&missing, &computed)?;
+
+        let mut computed = computed.into_iter();
+        for record in records.iter_mut().filter(|record| record.is_none()) {
+            *record = computed.next();
+        }
+        Ok(records.into_iter().flatten().collect())
+    }
+}
+
+/// A commit of the walked range.
+pub struct WalkedCommit<'r> {
+    pub commit: Commit<'r>,
+    /// The canonical email of the author.
+    pub author: String,
+    /// The commit time, in seconds since the epoch.
+    pub time: i64,
+    /// Whether the commit is selected by the filter.
+    pub selected: bool,
+    /// The diff against the first parent, unless no visitor looks at diffs.
+    pub diff: Option<CommitDiff>,
+    /// The churn of the files selected by the filter, or `None` when the commit is left out by
+    /// the filter, was not diffed, or only touched files the filter excludes.
+    pub churn: Option<CodeChurn>,
+}
+
+/// Walks the history of a range once and hands every commit to a set of analyzers.
+///
+/// Commits are visited oldest first. Each one is looked up and diffed once, and its author
+/// resolved once, however many analyzers look at it. Commits are only diffed when one of the
+/// visitors looks at diffs, and not even looked up when none looks at commits.
+///
+/// Diffs are computed in parallel, in batches, with one repository handle per worker thread.
+/// The walk is synchronous, so async callers should run it on a blocking thread. With a cache,
+/// only the commits it has not seen yet are diffed, and the authors and times of the others are
+/// read from their records.
+#[derive(Default)]
+pub struct Pipeline {
+    filter: CommitFilter,
+    aliases: HashMap<String, String>,
+    cache: Option<CommitCache>,
+}
+
+impl Pipeline {
+    /// Only analyzes the commits and files selected by the given filter.
+    pub fn with_filter(mut self, filter: CommitFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Merges the given author names or emails into canonical emails, on top of `.mailmap`.
+    pub fn with_aliases(mut self, aliases: HashMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Reads the records of commits from the given cache, and stores the ones it lacks.
+    pub fn with_cache(mut self, cache: CommitCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Walks the commits of a range, handing each one to the visitors.
+    ///
+    /// The range should follow only the first parent of merges when the filter does.
+    /// Returns the context to finish the analyzers with.
+    pub fn run<'r>(
+        &'r self, repo: &'r Repository, range: &'r CommitRange,
+        visitors: &mut [&mut dyn CommitVisitor],
+    ) -> Result<AnalysisContext<'r>, Box<dyn Error + Send + Sync>> {
+        let end = repo.find_commit(range.end)?;
+        let mut context = AnalysisContext {
+            repo,
+            start_commit: range.start_commit.clone().unwrap_or_default(),
+            end_commit: range.end_commit.clone(),
+            paths: self.filter.paths().at(repo, &end)?,
+            end,
+            commits: &range.commits,
+            filter: &self.filter,
+            identities: IdentityResolver::new(repo, &self.aliases)?,
+            cache: self.cache.as_ref(),
+        };
+
+        let coverage = visitors
+            .iter()
+            .map(|visitor| visitor.coverage())
+            .max()
+            .unwrap_or(Coverage::End);
+        if coverage == Coverage::End {
+            return Ok(context);
+        }
+
+        for batch in range.commits.rchunks(BATCH_SIZE) {
+            let mut commits = Vec::with_capacity(batch.len());
+            for id in batch.iter().rev() {
+                let commit = repo.find_commit(*id)?;
+                let selected = self.filter.includes(&commit);
+                if selected || coverage == Coverage::History {
+                    commits.push((commit, selected));
+                }
+            }
+            let records = if coverage >= Coverage::Diffs {
+                let ids: Vec<Oid> = commits.iter().map(|(commit, _)| commit.id()).collect();
+                context.records(&ids)?.into_iter().map(Some).collect()
+            } else {
+                vec![None; commits.len()]
+            };
+
+            for ((commit, selected), record) in commits.into_iter().zip(records) {
+                visit(&mut context, visitors, commit, selected, record)?;
+            }
+        }
+
+        Ok(context)
+    }
+}
+
+/// Hands a walked commit to the visitors that look at it, with its author and time taken from
+/// its record when it was diffed.
+fn visit<'r>(
+    context: &mut AnalysisContext<'r>, visitors: &mut [&mut dyn CommitVisitor], commit: Commit<'r>,
+    selected: bool, record: Option<CachedCommit>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (author, time, diff) = match record {
+        Some(record) => (
+            context
+                .identities
+                .resolve_parts(&record.author_name, &record.author_email),
+            record.time,
+            Some(record.diff),
+        ),
+        None => (context.identities.resolve(&commit.author()), commit.time().seconds(), None),
+    };
+    let churn = diff
+        .as_ref()
+        .filter(|_| selected)
+        .and_then(|diff| selected_churn(&diff.churn, &context.paths));
+    let commit = WalkedCommit {
+        commit,
+        author,
+        time,
+        selected,
+        diff,
+        churn,
+    };
+
+    for visitor in visitors.iter_mut() {
+        let visited = match visitor.coverage() {
+            Coverage::End => false,
+            Coverage::History => true,
+            Coverage::Commits | Coverage::Diffs => selected,
+        };
+        if visited {
+            visitor.visit(context, &commit)?;
+        }
+    }
+    Ok(())
+}
+
+/// Records commits in parallel, each worker thread opening its own handle on the repository.
+///
+/// The records are returned in the order of the given commits.
+fn record_commits(repo: &Repository, ids: &[Oid]) -> Result<Vec<CachedCommit>, git2::Error> {
+    let repo_path = repo.path();
+    ids.par_iter()
+        .map_init(
+            || Repository::open(repo_path),
+            |worker_repo, id| {
+                let worker_repo = worker_repo.as_ref().map_err(|e| {
+                    git2::Error::from_str(&format!("Failed to open repository: {}", e))
+                })?;
+                CachedCommit::new(worker_repo, &worker_repo.find_commit(*id)?)
+            },
+        )
+        .collect()
+}
+
+/// Drops the files left out by the path filter from a commit's churn, along with the whole
+/// commit when it only touched such files.
+fn selected_churn(churn: &CodeChurn, paths: &PathMatcher) -> Option<CodeChurn> {
+    let mut churn = churn.clone();
+    let touched_files = !churn.files().is_empty();
+    churn.retain_files(|file| paths.includes(file.path()));
+    (!touched_files || !churn.files().is_empty()).then_some(churn)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analyzers::{Analyzer, CodeChurnAnalyzer, ComplianceAnalyzer, OwnershipAnalyzer};
+    use crate::models::{ComplianceRules, RangeSpec};
+    use crate::repository::PathFilter;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    impl Pipeline {
+        /// Runs a single analyzer over the commits between two revisions of a repository.
+        pub(crate) fn analyze<A: Analyzer>(
+            &self, repo_path: &str, old_commit: &str, new_commit: &str, mut analyzer: A,
+        ) -> Result<A::Output, Box<dyn Error + Send + Sync>> {
+            let repo = Repository::open(repo_path)?;
+            let spec = RangeSpec {
+                start_commit: Some(old_commit.to_string()),
+                end_commit: Some(new_commit.to_string()),
+                ..RangeSpec::default()
+            };
+            let range = CommitRange::resolve(&repo, &spec, self.filter.follows_first_parent())?;
+            let mut context = self.run(&repo, &range, &mut [&mut analyzer])?;
+            analyzer.finish(&mut context)
+        }
+    }
+
+    #[test]
+    fn test_run_feeds_every_visitor_from_one_walk() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        test_repo.commit("feat: add entry point");
+        test_repo.write("docs/index.md", "# Docs\n");
+        let end = test_repo.commit("update docs");
+
+        let paths = PathFilter::new(&[], &["docs/**".to_string()]).unwrap();
+        let pipeline = Pipeline::default().with_filter(CommitFilter::default().with_paths(paths));
+        let mut code_churn = CodeChurnAnalyzer::default();
+        let mut compliance = ComplianceAnalyzer::new(ComplianceRules::default());
+        let mut ownership = OwnershipAnalyzer;
+        let spec = RangeSpec {
+            start_commit: Some(start.to_string()),
+            end_commit: Some(end.to_string()),
+            ..RangeSpec::default()
+        };
+        let range = CommitRange::resolve(&test_repo.repo, &spec, false).unwrap();
+        let mut context = pipeline
+            .run(&test_repo.repo, &range, &mut [&mut code_churn, &mut compliance, &mut ownership])
+            .unwrap();
+
+        // The docs commit is walked for its message but its churn is filtered out.
+        let churn = code_churn.finish(&mut context).unwrap();
+        assert_eq!(churn.len(), 1);
+        assert_eq!(churn[0].files()[0].path(), "src/main.rs");
+        let compliance = compliance.finish(&mut context).unwrap();
+        assert_eq!(compliance.checked, 2);
+        assert_eq!(compliance.compliant, 1);
+        let ownership = ownership.finish(&mut context).unwrap();
+        assert_eq!(ownership.lines, 2);
+    }
+
+    #[test]
+    fn test_record_commits_keeps_commit_order() {
+        let test_repo = TestRepo::new();
+        let ids: Vec<Oid> = (1..=20)
+            .map(|n| {
+                test_repo.write(&format!("src/file_{}.rs", n), "fn f() {}\n".repeat(n));
+                test_repo.commit(&format!("add file {}", n))
+            })
+            .collect();
+
+        let records = record_commits(&test_repo.repo, &ids).unwrap();
+        let additions: Vec<_> = records
+            .iter()
+            .map(|record| record.diff.churn.additions())
+            .collect();
+        assert_eq!(additions, (1..=20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_run_reads_diffs_from_cache() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        let main = test_repo.commit("add main");
+        test_repo.write("src/lib.rs", "pub fn lib() {}\n");
+        let lib = test_repo.commit("add lib");
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CommitCache::open(dir.path().join("commits.redb")).unwrap();
+        let analyze = |pipeline: Pipeline| {
+            pipeline
+                .analyze(
+                    test_repo.path(),
+                    &start.to_string(),
+                    &lib.to_string(),
+                    CodeChurnAnalyzer::default(),
+                )
+                .unwrap()
+        };
+        let churn = analyze(Pipeline::default().with_cache(cache.clone()));
+        assert_eq!(churn[0].files()[0].path(), "src/lib.rs");
+
+        // Cached records are trusted over the repository.
+        let main_record = cache.get(&[main]).unwrap().remove(0).unwrap();
+        cache.insert(&[lib], &[main_record]).unwrap();
+        let churn = analyze(Pipeline::default().with_cache(cache));
+        assert_eq!(churn[0].files()[0].path(), "src/main.rs");
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::rc::Rc;
+
+use git2::Oid;
+
+use crate::models::{AuthorRework, FileRework, FileStatus, Rework};
+use crate::repository::{CommitDiff, PathMatcher};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Measures how much code added in a range is rewritten or deleted within a time window.
+///
+/// Lines are tracked along the first-parent chain of the end of the range, through each
+/// commit's diff against its first parent, oldest commit first. The commits of merged branches
+/// are left out, as their diffs do not line up with the lines of the chain: merge commits stand
+/// in for the branches they merge, and the lines they bring in are credited to their author.
+pub struct ReworkAnalyzer {
+    window_days: u32,
+    tracker: ReworkTracker,
+    /// The commits of the range on the first-parent chain of its end, found on the first visit.
+    chain: Option<HashSet<Oid>>,
+}
+
+impl ReworkAnalyzer {
+    /// Creates a new `ReworkAnalyzer` counting rewrites up to `window_days` after a line landed.
+    pub fn new(window_days: u32) -> Self {
+        Self {
+            window_days,
+            tracker: ReworkTracker::default(),
+            chain: None,
+        }
+    }
+}
+
+/// Lists the commits of the range that are on the first-parent chain of its end.
+fn first_parent_chain(context: &AnalysisContext) -> HashSet<Oid> {
+    let range: HashSet<Oid> = context.commits.iter().copied().collect();
+    let mut chain = HashSet::new();
+    let mut commit = Some(context.end.clone());
+    while let Some(current) = commit {
+        if range.contains(&current.id()) {
+            chain.insert(current.id());
+        } else if !chain.is_empty() {
+            // The chain left the range, so the rest of it is older than the range.
+            break;
+        }
+        commit = current.parent(0).ok();
+    }
+    chain
+}
+
+/// A line added inside the range, with the canonical email of its author.
+struct Line {
+    author: Rc<str>,
+    path: Rc<str>,
+    time: i64,
+}
+
+#[derive(Default)]
+struct Tally {
+    added: usize,
+    reworked: usize,
+}
+
+impl Tally {
+    fn ratio(&self) -> f64 {
+        self.reworked as f64 / self.added.max(1) as f64
+    }
+}
+
+#[derive(Default)]
+struct ReworkTracker {
+    /// The current lines of each touched file; `None` for lines that predate the range.
+    files: HashMap<String, Vec<Option<Line>>>,
+    authors: HashMap<Rc<str>, Tally>,
+    paths: HashMap<Rc<str>, Tally>,
+}
+
+impl ReworkTracker {
+    /// Records that `line` was rewritten or deleted at `time`.
+    fn remove(&mut self, line: Option<Line>, time: i64, window: i64) {
+        if let Some(line) = line {
+            if time - line.time <= window {
+                self.authors.entry(line.author).or_default().reworked += 1;
+                self.paths.entry(line.path).or_default().reworked += 1;
+            }
+        }
+    }
+
+    /// Applies a commit diff, attributing added lines to `author` at `time`.
+    ///
+    /// Lines added without an author are tracked as if they predated the range.
+    fn apply(
+        &mut self, diff: &CommitDiff, author: Option<&str>, time: i64, window: i64,
+        paths: &PathMatcher,
+    ) {
+        let author: Option<Rc<str>> = author.map(Rc::from);
+
+        for (file, hunks) in diff.churn.files().iter().zip(&diff.hunks) {
+            let new_path = file.path();
+            let old_path = file.old_path().unwrap_or(new_path);
+            if !paths.includes(old_path) && !paths.includes(new_path) {
+                continue;
+            }
+            let mut lines = self.files.remove(old_path).unwrap_or_default();
+
+            if file.status() == FileStatus::Deleted {
+                for line in lines.drain(..) {
+                    self.remove(line, time, window);
+                }
+                continue;
+            }
+
+            let path: Rc<str> = Rc::from(new_path);
+
+            // Splice hunks from the bottom up so earlier line numbers stay valid.
+            for hunk in hunks.iter().rev() {
+                // Without context, a pure insertion starts after `old_start`.
+                let start = if hunk.old_lines == 0 {
+                    hunk.old_start
+                } else {
+                    hunk.old_start - 1
+                };
+                if lines.len() < start + hunk.old_lines {
+                    lines.resize_with(start + hunk.old_lines, || None);
+                }
+
+                let added = (0..hunk.new_lines).map(|_| {
+                    author.as_ref().map(|author| Line {
+                        author: author.clone(),
+                        path: path.clone(),
+                        time,
+                    })
+                });
+                let removed: Vec<_> = lines.splice(start..start + hunk.old_lines, added).collect();
+                for line in removed {
+                    self.remove(line, time, window);
+                }
+
+                if let Some(author) = &author {
+                    self.authors.entry(author.clone()).or_default().added += hunk.new_lines;
+                    self.paths.entry(path.clone()).or_default().added += hunk.new_lines;
+                }
+            }
+
+            self.files.insert(new_path.to_string(), lines);
+        }
+    }
+}
+
+impl CommitVisitor for ReworkAnalyzer {
+    fn coverage(&self) -> Coverage {
+        // Commits left out by the filter are still applied so that line positions stay accurate.
+        Coverage::History
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(diff) = &commit.diff else {
+            return Ok(());
+        };
+        let chain = self
+            .chain
+            .get_or_insert_with(|| first_parent_chain(context));
+        if !chain.contains(&commit.commit.id()) {
+            return Ok(());
+        }
+
+        let window = i64::from(self.window_days) * SECONDS_PER_DAY;
+        let author = commit.selected.then_some(commit.author.as_str());
+        self.tracker
+            .apply(diff, author, commit.time, window, &context.paths);
+        Ok(())
+    }
+}
+
+impl Analyzer for ReworkAnalyzer {
+    type Output = Rework;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let tracker = self.tracker;
+        let identities = &context.identities;
+
+        let mut authors: Vec<_> = tracker
+            .authors
+            .iter()
+            .map(|(email, tally)| AuthorRework {
+                author: identities.name(email).to_string(),
+                email: email.to_string(),
+                added: tally.added,
+                reworked: tally.reworked,
+                rework_ratio: tally.ratio(),
+            })
+            .collect();
+        authors.sort_by(|a, b| b.reworked.cmp(&a.reworked).then(a.author.cmp(&b.author)));
+
+        let mut files: Vec<_> = tracker
+            .paths
+            .iter()
+            .map(|(path, tally)| FileRework {
+                path: path.to_string(),
+                added: tally.added,
+                reworked: tally.reworked,
+                rework_ratio: tally.ratio(),
+            })
+            .collect();
+        files.sort_by(|a, b| b.reworked.cmp(&a.reworked).then(a.path.cmp(&b.path)));
+
+        let total = Tally {
+            added: authors.iter().map(|a| a.added).sum(),
+            reworked: authors.iter().map(|a| a.reworked).sum(),
+        };
+
+        Ok(Rework {
+            window_days: self.window_days,
+            added: total.added,
+            reworked: total.reworked,
+            rework_ratio: total.ratio(),
+            authors,
+            files,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_rework_within_window() {
+        let test_repo = TestRepo::new();
+        let day = |n: i64| Time::new(1_700_000_000 + n * SECONDS_PER_DAY, 0);
+
+        test_repo.write("base.txt", "base\n");
+        let start = test_repo
+            .commit_as("Jane Doe", "jane@example.com", day(0), "base")
+            .to_string();
+
+        test_repo.write("lib.rs", "a\nb\nc\nd\n");
+        test_repo.write("old.rs", "x\ny\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", day(1), "add");
+
+        // Rewrites one of Jane's lines soon after it landed.
+        test_repo.write("lib.rs", "a\nB\nc\nd\ne\n");
+        test_repo.commit_as("John Roe", "john@example.com", day(3), "rewrite");
+
+        // Deleting a file long after the window is not rework.
+        test_repo.remove("old.rs");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", day(40), "delete")
+            .to_string();
+
+        let rework = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, ReworkAnalyzer::new(21))
+            .unwrap();
+
+        assert_eq!(rework.added, 8);
+        assert_eq!(rework.reworked, 1);
+
+        let jane = rework
+            .authors
+            .iter()
+            .find(|a| a.author == "Jane Doe")
+            .unwrap();
+        assert_eq!((jane.added, jane.reworked), (6, 1));
+        let john = rework
+            .authors
+            .iter()
+            .find(|a| a.author == "John Roe")
+            .unwrap();
+        assert_eq!((john.added, john.reworked), (2, 0));
+
+        let lib = rework.files.iter().find(|f| f.path == "lib.rs").unwrap();
+        assert_eq!((lib.added, lib.reworked), (6, 1));
+        assert_eq!(rework.files[0].path, "lib.rs");
+    }
+
+    #[test]
+    fn test_rework_follows_first_parent_chain_across_merges() {
+        let test_repo = TestRepo::new();
+        let day = |n: i64| Time::new(1_700_000_000 + n * SECONDS_PER_DAY, 0);
+
+        test_repo.write("lib.rs", "1\n2\n3\n4\n5\n6\n");
+        let base = test_repo.commit_as("Jane Doe", "jane@example.com", day(0), "base");
+
+        test_repo.write("lib.rs", "1\n2\nx\ny\n3\n4\n5\n6\n");
+        let main = test_repo.commit_as("Jane Doe", "jane@example.com", day(1), "insert");
+
+        // A side branch editing the same file, at lines the main branch has since moved.
+        test_repo.reset(base);
+        test_repo.write("lib.rs", "1\n2\n3\nfour\n5\n6\n");
+        let side = test_repo.commit_as("Bob Poe", "bob@example.com", day(2), "spell out four");
+        test_repo.reset(main);
+        test_repo.merge(side, "Merge branch 'side'");
+
+        // Rewrites one of the lines Jane inserted.
+        test_repo.write("lib.rs", "1\n2\nx\nY\n3\nfour\n5\n6\n");
+        let end = test_repo.commit_as("John Roe", "john@example.com", day(3), "rewrite");
+
+        let rework = Pipeline::default()
+            .analyze(test_repo.path(), &base.to_string(), &end.to_string(), ReworkAnalyzer::new(21))
+            .unwrap();
+
+        // The side branch does not touch the lines of the main branch, and its line is credited
+        // to the merge.
+        assert_eq!((rework.added, rework.reworked), (4, 1));
+        assert!(rework.authors.iter().all(|a| a.author != "Bob Poe"));
+        let jane = rework
+            .authors
+            .iter()
+            .find(|a| a.author == "Jane Doe")
+            .unwrap();
+        assert_eq!((jane.added, jane.reworked), (3, 1));
+    }
+}
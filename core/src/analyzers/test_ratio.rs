@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::models::{AuthorTestRatio, CommitTestRatio, TestRatio};
+use crate::repository::{GitAttributes, Hunk, LanguageDetector, TestClassifier};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Splits the churn of a range of commits between test and production code, and flags the
+/// commits that change production code without changing any test.
+///
+/// Languages are detected with the `.gitattributes` of the end commit, like the language
+/// breakdown does.
+pub struct TestRatioAnalyzer {
+    classifier: TestClassifier,
+    /// Loaded with the attributes of the end commit when the first commit is visited.
+    detector: Option<LanguageDetector>,
+    total: Churn,
+    authors: HashMap<String, AuthorTally>,
+    commits: Vec<CommitTestRatio>,
+}
+
+impl TestRatioAnalyzer {
+    /// Creates a new `TestRatioAnalyzer` telling tests apart with the given classifier.
+    pub fn new(classifier: TestClassifier) -> Self {
+        Self {
+            classifier,
+            detector: None,
+            total: Churn::default(),
+            authors: HashMap::new(),
+            commits: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+struct Churn {
+    test_additions: usize,
+    test_deletions: usize,
+    production_additions: usize,
+    production_deletions: usize,
+}
+
+impl Churn {
+    fn add(&mut self, other: Churn) {
+        self.test_additions += other.test_additions;
+        self.test_deletions += other.test_deletions;
+        self.production_additions += other.production_additions;
+        self.production_deletions += other.production_deletions;
+    }
+
+    fn test(&self) -> usize {
+        self.test_additions + self.test_deletions
+    }
+
+    fn production(&self) -> usize {
+        self.production_additions + self.production_deletions
+    }
+
+    fn ratio(&self) -> Option<f64> {
+        (self.production() > 0).then(|| self.test() as f64 / self.production() as f64)
+    }
+
+    fn untested(&self) -> bool {
+        self.production() > 0 && self.test() == 0
+    }
+}
+
+#[derive(Default)]
+struct AuthorTally {
+    commits: usize,
+    untested_commits: usize,
+    churn: Churn,
+}
+
+/// Counts the lines of hunks, given by their start and length, that lie at or after `from`.
+fn lines_from(hunks: &[Hunk], from: usize, side: impl Fn(&Hunk) -> (usize, usize)) -> usize {
+    hunks
+        .iter()
+        .map(|hunk| {
+            let (start, lines) = side(hunk);
+            (start + lines).saturating_sub(start.max(from))
+        })
+        .sum()
+}
+
+impl CommitVisitor for TestRatioAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (Some(_), Some(diff)) = (&commit.churn, &commit.diff) else {
+            return Ok(());
+        };
+        let detector = match &mut self.detector {
+            Some(detector) => detector,
+            None => self
+                .detector
+                .insert(LanguageDetector::new(GitAttributes::load(context.repo, &context.end)?)),
+        };
+        let tree = commit.commit.tree()?;
+        let parent_tree = match commit.commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+
+        let mut churn = Churn::default();
+        for (file, hunks) in diff.churn.files().iter().zip(&diff.hunks) {
+            if !context.paths.includes(file.path()) {
+                continue;
+            }
+            let Some(language) = detector.detect(context.repo, &tree, file.path()) else {
+                continue;
+            };
+            match self.classifier.is_test_file(language, file.path()) {
+                None => {}
+                Some(true) => churn.add(Churn {
+                    test_additions: file.additions(),
+                    test_deletions: file.deletions(),
+                    ..Churn::default()
+                }),
+                Some(false) => {
+                    // Inline test modules make part of a production file test code.
+                    let added = self
+                        .classifier
+                        .test_module_start(context.repo, &tree, language, file.path())
+                        .map_or(0, |from| {
+                            lines_from(hunks, from, |hunk| (hunk.new_start, hunk.new_lines))
+                        });
+                    let old_path = file.old_path().unwrap_or(file.path());
+                    let deleted = parent_tree
+                        .as_ref()
+                        .and_then(|parent_tree| {
+                            self.classifier.test_module_start(
+                                context.repo,
+                                parent_tree,
+                                language,
+                                old_path,
+                            )
+                        })
+                        .map_or(0, |from| {
+                            lines_from(hunks, from, |hunk| (hunk.old_start, hunk.old_lines))
+                        });
+                    churn.add(Churn {
+                        test_additions: added,
+                        test_deletions: deleted,
+                        production_additions: file.additions().saturating_sub(added),
+                        production_deletions: file.deletions().saturating_sub(deleted),
+                    });
+                }
+            }
+        }
+        if churn.test() + churn.production() == 0 {
+            return Ok(());
+        }
+
+        self.total.add(churn);
+        let tally = self.authors.entry(commit.author.clone()).or_default();
+        tally.commits += 1;
+        tally.untested_commits += usize::from(churn.untested());
+        tally.churn.add(churn);
+        self.commits.push(CommitTestRatio {
+            commit: commit.commit.id().to_string(),
+            author: context.identities.name(&commit.author).to_string(),
+            email: commit.author.clone(),
+            test_additions: churn.test_additions,
+            test_deletions: churn.test_deletions,
+            production_additions: churn.production_additions,
+            production_deletions: churn.production_deletions,
+            untested: churn.untested(),
+        });
+        Ok(())
+    }
+}
+
+impl Analyzer for TestRatioAnalyzer {
+    type Output = TestRatio;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let mut authors: Vec<AuthorTestRatio> = self
+            .authors
+            .into_iter()
+            .map(|(email, tally)| AuthorTestRatio {
+                author: context.identities.name(&email).to_string(),
+                email,
+                commits: tally.commits,
+                untested_commits: tally.untested_commits,
+                test_additions: tally.churn.test_additions,
+                test_deletions: tally.churn.test_deletions,
+                production_additions: tally.churn.production_additions,
+                production_deletions: tally.churn.production_deletions,
+                test_ratio: tally.churn.ratio(),
+            })
+            .collect();
+        authors.sort_by(|a, b| {
+            let churn = |author: &AuthorTestRatio| {
+                author.test_additions
+                    + author.test_deletions
+                    + author.production_additions
+                    + author.production_deletions
+            };
+            churn(b).cmp(&churn(a)).then(a.author.cmp(&b.author))
+        });
+
+        // Commits are visited oldest first.
+        let mut commits = self.commits;
+        commits.reverse();
+
+        Ok(TestRatio {
+            test_additions: self.total.test_additions,
+            test_deletions: self.total.test_deletions,
+            production_additions: self.total.production_additions,
+            production_deletions: self.total.production_deletions,
+            test_ratio: self.total.ratio(),
+            untested_commits: commits.iter().filter(|commit| commit.untested).count(),
+            authors,
+            commits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_test_ratio_splits_test_and_production_churn() {
+        let test_repo = TestRepo::new();
+        test_repo.write("src/lib.rs", "pub fn a() {}\n\n#[cfg(test)]\nmod tests {\n}\n");
+        let start = test_repo.commit("initial");
+
+        test_repo.write(
+            "src/lib.rs",
+            "pub fn a() {}\npub fn b() {}\n\n#[cfg(test)]\nmod tests {\n    fn test_b() {}\n}\n",
+        );
+        test_repo.write("tests/api.rs", "fn test_api() {}\n");
+        test_repo.write("README.md", "# Docs\n");
+        test_repo.commit("feat: add b");
+        test_repo.write("server.go", "package main\n");
+        let untested = test_repo.commit_as(
+            "Octo Cat",
+            "octocat@example.com",
+            Time::new(1_700_000_000, 0),
+            "feat: add a server",
+        );
+        test_repo.write("README.md", "# Docs\n\nMore.\n");
+        let end = test_repo.commit("docs: expand readme");
+
+        let analyzer = TestRatioAnalyzer::new(TestClassifier::new(&HashMap::new()).unwrap());
+        let ratio = Pipeline::default()
+            .analyze(test_repo.path(), &start.to_string(), &end.to_string(), analyzer)
+            .unwrap();
+
+        // The docs commit changes no code.
+        assert_eq!(ratio.commits.len(), 2);
+        assert_eq!(ratio.commits[0].commit, untested.to_string());
+        assert!(ratio.commits[0].untested);
+        assert_eq!(ratio.commits[0].production_additions, 1);
+        let tested = &ratio.commits[1];
+        assert!(!tested.untested);
+        assert_eq!((tested.test_additions, tested.production_additions), (2, 1));
+
+        assert_eq!((ratio.test_additions, ratio.production_additions), (2, 2));
+        assert_eq!(ratio.test_ratio, Some(1.0));
+        assert_eq!(ratio.untested_commits, 1);
+        assert_eq!(ratio.authors.len(), 2);
+        assert_eq!(ratio.authors[0].email, "jane@example.com");
+        assert_eq!(ratio.authors[1].untested_commits, 1);
+        assert_eq!(ratio.authors[1].test_ratio, Some(0.0));
+    }
+}
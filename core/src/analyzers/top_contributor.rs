@@ -1,45 +1,280 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
-use async_trait::async_trait;
-use git2::{Oid, Repository};
+use crate::models::{CoAuthorCredit, Contributor};
+use crate::repository::{co_authors, BotDetector};
 
-use crate::models::Contributor;
-use crate::repository::{calculate_code_churn, create_revwalk};
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
 
-use super::Analyzer;
+/// Aggregates the activity of each contributor, including co-authors named in
+/// `Co-authored-by:` trailers.
+#[derive(Default)]
+pub struct TopContributorsAnalyzer {
+    co_author_credit: CoAuthorCredit,
+    bot_detector: BotDetector,
+    contributors: HashMap<String, ContributorActivity>,
+}
 
-pub struct TopContributorsAnalyzer;
+impl TopContributorsAnalyzer {
+    /// Sets how commits with co-authors are credited.
+    pub fn with_co_author_credit(mut self, co_author_credit: CoAuthorCredit) -> Self {
+        self.co_author_credit = co_author_credit;
+        self
+    }
 
-#[async_trait]
-impl Analyzer for TopContributorsAnalyzer {
-    type Output = Vec<Contributor>;
+    /// Sets the detector used to flag contributors as bots.
+    pub fn with_bot_detector(mut self, bot_detector: BotDetector) -> Self {
+        self.bot_detector = bot_detector;
+        self
+    }
+}
 
-    async fn analyze(
-        &self, repo_path: &str, old_commit: &str, new_commit: &str,
-    ) -> Result<Self::Output, Box<dyn Error>> {
-        let repo = Repository::open(repo_path)?;
-        let revwalk = create_revwalk(&repo, old_commit, new_commit)?;
-        let churn_data = calculate_code_churn(&repo, revwalk)?;
+#[derive(Default)]
+struct ContributorActivity {
+    // Fractional when credit for a commit is split between co-authors.
+    commits: f64,
+    additions: f64,
+    deletions: f64,
+    files: HashSet<String>,
+}
 
-        let mut contributors = HashMap::new();
+impl CommitVisitor for TopContributorsAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
 
-        for churn in churn_data {
-            let commit_id = churn.commit().to_string();
-            let commit = repo.find_commit(Oid::from_str(&commit_id)?)?;
-            let author_name = commit.author().name().unwrap_or("Unknown").to_string();
+    fn visit(
+        &mut self, context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(churn) = &commit.churn else {
+            return Ok(());
+        };
 
-            let entry = contributors
-                .entry(author_name.clone())
-                .or_insert_with(|| Contributor::new(author_name.clone(), 0));
-            entry.add_commits(churn.additions() as i32 + churn.deletions() as i32);
+        let mut authors = vec![commit.author.clone()];
+        for (name, email) in co_authors(commit.commit.message().unwrap_or_default()) {
+            if context
+                .filter
+                .bots()
+                .is_some_and(|bots| bots.is_bot(&name, &email))
+            {
+                continue;
+            }
+            let co_author = context.identities.resolve_parts(&name, &email);
+            if !authors.contains(&co_author) {
+                authors.push(co_author);
+            }
         }
 
-        let top_contributors: Vec<_> = contributors
+        let share = match self.co_author_credit {
+            CoAuthorCredit::Full => 1.0,
+            CoAuthorCredit::Split => 1.0 / authors.len() as f64,
+        };
+        for email in authors {
+            let entry = self.contributors.entry(email).or_default();
+            entry.commits += share;
+            entry.additions += share * churn.additions() as f64;
+            entry.deletions += share * churn.deletions() as f64;
+            entry
+                .files
+                .extend(churn.files().iter().map(|file| file.path().to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Analyzer for TopContributorsAnalyzer {
+    type Output = Vec<Contributor>;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let top_contributors: Vec<_> = self
+            .contributors
             .into_iter()
-            .map(|(_, contributor)| contributor)
+            .map(|(email, activity)| {
+                let mut contributor =
+                    Contributor::new(context.identities.name(&email).to_string(), email);
+                contributor.commits = activity.commits.round() as i32;
+                contributor.additions = activity.additions.round() as i32;
+                contributor.deletions = activity.deletions.round() as i32;
+                contributor.files_touched = activity.files.len() as i32;
+                contributor.is_bot = self
+                    .bot_detector
+                    .is_bot(&contributor.username, &contributor.email);
+                contributor
+            })
             .collect();
 
         Ok(top_contributors)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::models::ContributorRanking;
+    use crate::repository::CommitFilter;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_top_contributors_merges_identities() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write(".mailmap", "Jane Doe <jane@example.com> <jane@laptop.local>\n");
+        let start = test_repo.commit("add mailmap").to_string();
+
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "one");
+        test_repo.write("b.txt", "b\n");
+        test_repo.commit_as("jane", "jane@laptop.local", time, "two");
+        test_repo.write("c.txt", "c\n");
+        test_repo.commit_as("Jane Doe (work laptop)", "jdoe@work.example", time, "three");
+        test_repo.write("d.txt", "d\n");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", time, "four")
+            .to_string();
+
+        let aliases =
+            HashMap::from([("jdoe@work.example".to_string(), "jane@example.com".to_string())]);
+        let mut contributors = Pipeline::default()
+            .with_aliases(aliases)
+            .analyze(test_repo.path(), &start, &end, TopContributorsAnalyzer::default())
+            .unwrap();
+        contributors.sort_by(|a, b| a.email.cmp(&b.email));
+
+        assert_eq!(contributors.len(), 2);
+        assert_eq!(contributors[0].username, "Jane Doe");
+        assert_eq!(contributors[0].email, "jane@example.com");
+        assert_eq!(contributors[0].commits, 3);
+        assert_eq!(contributors[1].username, "John Roe");
+    }
+
+    #[test]
+    fn test_contributor_counts_and_ranking() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "one");
+        test_repo.write("a.txt", "b\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "two");
+        test_repo.write("b.txt", "1\n2\n3\n");
+        test_repo.write("c.txt", "1\n2\n3\n");
+        test_repo.remove("README.md");
+        let end = test_repo
+            .commit_as("John Roe", "john@example.com", time, "three")
+            .to_string();
+
+        let mut contributors = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, TopContributorsAnalyzer::default())
+            .unwrap();
+
+        ContributorRanking::Commits.sort(&mut contributors);
+        let jane = &contributors[0];
+        assert_eq!(jane.username, "Jane Doe");
+        assert_eq!(
+            (jane.commits, jane.additions, jane.deletions, jane.files_touched),
+            (2, 2, 1, 1)
+        );
+
+        ContributorRanking::Additions.sort(&mut contributors);
+        let john = &contributors[0];
+        assert_eq!(john.username, "John Roe");
+        assert_eq!(
+            (john.commits, john.additions, john.deletions, john.files_touched),
+            (1, 6, 1, 3)
+        );
+    }
+
+    #[test]
+    fn test_co_author_credit() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("a.txt", "1\n2\n3\n4\n");
+        test_repo.commit_as(
+            "Jane Doe",
+            "jane@example.com",
+            time,
+            "Pair on a\n\nCo-authored-by: John Roe <john@example.com>\n",
+        );
+        test_repo.write("b.txt", "1\n2\n");
+        let end = test_repo
+            .commit_as("Jane Doe", "jane@example.com", time, "Solo on b")
+            .to_string();
+
+        let contributors = |credit| {
+            let analyzer = TopContributorsAnalyzer::default().with_co_author_credit(credit);
+            let mut contributors = Pipeline::default()
+                .analyze(test_repo.path(), &start, &end, analyzer)
+                .unwrap();
+            ContributorRanking::Commits.sort(&mut contributors);
+            contributors
+                .into_iter()
+                .map(|c| (c.username, c.commits, c.additions))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            contributors(CoAuthorCredit::Full),
+            [
+                ("Jane Doe".to_string(), 2, 6),
+                ("John Roe".to_string(), 1, 4)
+            ]
+        );
+        assert_eq!(
+            contributors(CoAuthorCredit::Split),
+            [
+                ("Jane Doe".to_string(), 2, 4),
+                ("John Roe".to_string(), 1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bots_flagged_or_excluded() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_700_000_000, 0);
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "feature");
+        test_repo.write("Cargo.lock", "lots\nof\nlines\n");
+        let end = test_repo
+            .commit_as(
+                "dependabot[bot]",
+                "49699333+dependabot[bot]@users.noreply.github.com",
+                time,
+                "Bump serde",
+            )
+            .to_string();
+
+        let contributors = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, TopContributorsAnalyzer::default())
+            .unwrap();
+        assert_eq!(contributors.len(), 2);
+        assert!(contributors
+            .iter()
+            .all(|c| c.is_bot == (c.username == "dependabot[bot]")));
+
+        let contributors = Pipeline::default()
+            .with_filter(CommitFilter::default().with_excluded_bots(Some(BotDetector::default())))
+            .analyze(test_repo.path(), &start, &end, TopContributorsAnalyzer::default())
+            .unwrap();
+        assert_eq!(contributors.len(), 1);
+        assert_eq!(contributors[0].username, "Jane Doe");
+    }
+}
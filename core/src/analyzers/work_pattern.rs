@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+use crate::models::{
+    AuthorWorkPattern, CommitHistogram, TeamWorkPattern, WorkPatterns, WorkingHours,
+};
+
+use super::{AnalysisContext, Analyzer, CommitVisitor, Coverage, WalkedCommit};
+
+/// Builds histograms of commit activity by hour of day and day of week.
+///
+/// Times are taken in each author's local time, using the timezone offset recorded in the
+/// commit, so that after-hours work can be spotted across timezones.
+pub struct WorkPatternAnalyzer {
+    working_hours: WorkingHours,
+    teams: HashMap<String, Vec<String>>,
+    overall: HistogramAccumulator,
+    authors: HashMap<String, HistogramAccumulator>,
+}
+
+impl WorkPatternAnalyzer {
+    /// Creates a new `WorkPatternAnalyzer` flagging commits outside of `working_hours`.
+    pub fn new(working_hours: WorkingHours) -> Self {
+        Self {
+            working_hours,
+            teams: HashMap::new(),
+            overall: HistogramAccumulator::default(),
+            authors: HashMap::new(),
+        }
+    }
+
+    /// Also reports a histogram per team, given as team names mapped to member emails.
+    ///
+    /// Members are resolved through `.mailmap` and the aliases, so a person listed under
+    /// several of their emails is counted once.
+    pub fn with_teams(mut self, teams: HashMap<String, Vec<String>>) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    fn is_after_hours(&self, time: &DateTime<FixedOffset>) -> bool {
+        let weekend = time.weekday().number_from_monday() > 5;
+        let hour = time.hour();
+        (weekend && !self.working_hours.weekends)
+            || hour < self.working_hours.start_hour
+            || hour >= self.working_hours.end_hour
+    }
+}
+
+#[derive(Default)]
+struct HistogramAccumulator {
+    by_hour: [usize; 24],
+    by_weekday: [usize; 7],
+    after_hours: usize,
+}
+
+impl HistogramAccumulator {
+    fn add(&mut self, time: &DateTime<FixedOffset>, after_hours: bool) {
+        self.by_hour[time.hour() as usize] += 1;
+        self.by_weekday[time.weekday().num_days_from_monday() as usize] += 1;
+        self.after_hours += usize::from(after_hours);
+    }
+
+    fn merge(&mut self, other: &HistogramAccumulator) {
+        for (hour, count) in self.by_hour.iter_mut().zip(other.by_hour) {
+            *hour += count;
+        }
+        for (day, count) in self.by_weekday.iter_mut().zip(other.by_weekday) {
+            *day += count;
+        }
+        self.after_hours += other.after_hours;
+    }
+
+    fn into_histogram(self) -> CommitHistogram {
+        let commits: usize = self.by_hour.iter().sum();
+        CommitHistogram {
+            commits,
+            by_hour: self.by_hour.to_vec(),
+            by_weekday: self.by_weekday.to_vec(),
+            after_hours: self.after_hours,
+            after_hours_ratio: self.after_hours as f64 / commits.max(1) as f64,
+        }
+    }
+}
+
+impl CommitVisitor for WorkPatternAnalyzer {
+    fn coverage(&self) -> Coverage {
+        Coverage::Diffs
+    }
+
+    fn visit(
+        &mut self, _context: &mut AnalysisContext, commit: &WalkedCommit,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if commit.churn.is_none() {
+            return Ok(());
+        }
+        let when = commit.commit.author().when();
+        let offset = FixedOffset::east_opt(when.offset_minutes() * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+        let time = DateTime::from_timestamp(when.seconds(), 0)
+            .unwrap_or_default()
+            .with_timezone(&offset);
+
+        let after_hours = self.is_after_hours(&time);
+        self.overall.add(&time, after_hours);
+        self.authors
+            .entry(commit.author.clone())
+            .or_default()
+            .add(&time, after_hours);
+        Ok(())
+    }
+}
+
+impl Analyzer for WorkPatternAnalyzer {
+    type Output = WorkPatterns;
+
+    fn finish(
+        self, context: &mut AnalysisContext,
+    ) -> Result<Self::Output, Box<dyn Error + Send + Sync>> {
+        let authors = self.authors;
+        let mut teams: Vec<_> = self
+            .teams
+            .iter()
+            .map(|(team, members)| {
+                let members: HashSet<String> = members
+                    .iter()
+                    .map(|member| context.identities.resolve_member(member))
+                    .collect();
+                let mut histogram = HistogramAccumulator::default();
+                for member in &members {
+                    if let Some(author) = authors.get(member) {
+                        histogram.merge(author);
+                    }
+                }
+                TeamWorkPattern {
+                    team: team.clone(),
+                    histogram: histogram.into_histogram(),
+                }
+            })
+            .collect();
+        teams.sort_by(|a, b| a.team.cmp(&b.team));
+
+        let mut authors: Vec<_> = authors
+            .into_iter()
+            .map(|(email, histogram)| AuthorWorkPattern {
+                author: context.identities.name(&email).to_string(),
+                email,
+                histogram: histogram.into_histogram(),
+            })
+            .collect();
+        authors.sort_by(|a, b| {
+            b.histogram
+                .after_hours
+                .cmp(&a.histogram.after_hours)
+                .then(a.author.cmp(&b.author))
+        });
+
+        Ok(WorkPatterns {
+            working_hours: self.working_hours,
+            overall: self.overall.into_histogram(),
+            authors,
+            teams,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::analyzers::Pipeline;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_work_patterns_in_local_time() {
+        let test_repo = TestRepo::new();
+        // Monday 2024-07-01T08:00:00Z.
+        let monday = 1_719_820_800;
+
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+
+        // 10:00 local time in Amsterdam (UTC+2).
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", Time::new(monday, 120), "one");
+        // 23:00 local time in New York (UTC-4).
+        test_repo.write("b.txt", "b\n");
+        let late = monday + 19 * 60 * 60;
+        test_repo.commit_as("John Roe", "john@example.com", Time::new(late, -240), "two");
+        // Saturday afternoon in Amsterdam.
+        test_repo.write("c.txt", "c\n");
+        let saturday = monday + 5 * 24 * 60 * 60 + 6 * 60 * 60;
+        let end = test_repo
+            .commit_as("Jane Doe", "jane@example.com", Time::new(saturday, 120), "three")
+            .to_string();
+
+        let teams = HashMap::from([("core".to_string(), vec!["jane@example.com".to_string()])]);
+        let analyzer = WorkPatternAnalyzer::new(WorkingHours::default()).with_teams(teams);
+        let patterns = Pipeline::default()
+            .analyze(test_repo.path(), &start, &end, analyzer)
+            .unwrap();
+
+        assert_eq!(patterns.overall.commits, 3);
+        assert_eq!(patterns.overall.after_hours, 2);
+        assert_eq!(patterns.overall.by_hour[10], 1);
+        assert_eq!(patterns.overall.by_hour[23], 1);
+        assert_eq!(patterns.overall.by_hour[16], 1);
+        assert_eq!(patterns.overall.by_weekday, [2, 0, 0, 0, 0, 1, 0]);
+
+        let team = &patterns.teams[0];
+        assert_eq!((team.histogram.commits, team.histogram.after_hours), (2, 1));
+
+        let john = patterns
+            .authors
+            .iter()
+            .find(|a| a.author == "John Roe")
+            .unwrap();
+        assert_eq!(john.histogram.after_hours_ratio, 1.0);
+    }
+
+    #[test]
+    fn test_team_members_are_resolved_through_aliases() {
+        let test_repo = TestRepo::new();
+        let time = Time::new(1_719_820_800, 0);
+        test_repo.write("README.md", "readme\n");
+        let start = test_repo.commit("initial").to_string();
+        test_repo.write("a.txt", "a\n");
+        test_repo.commit_as("Jane Doe", "jane@example.com", time, "one");
+        test_repo.write("b.txt", "b\n");
+        let end = test_repo
+            .commit_as("Jane Doe", "jane@laptop.local", time, "two")
+            .to_string();
+
+        // One team only knows Jane by her alias, the other under both of her emails.
+        let teams = HashMap::from([
+            ("core".to_string(), vec!["jane@laptop.local".to_string()]),
+            (
+                "platform".to_string(),
+                vec![
+                    "Jane@Example.com".to_string(),
+                    "jane@laptop.local".to_string(),
+                ],
+            ),
+        ]);
+        let aliases =
+            HashMap::from([("jane@laptop.local".to_string(), "jane@example.com".to_string())]);
+        let analyzer = WorkPatternAnalyzer::new(WorkingHours::default()).with_teams(teams);
+        let patterns = Pipeline::default()
+            .with_aliases(aliases)
+            .analyze(test_repo.path(), &start, &end, analyzer)
+            .unwrap();
+
+        assert_eq!(patterns.authors.len(), 1);
+        assert_eq!(patterns.teams[0].histogram.commits, 2);
+        assert_eq!(patterns.teams[1].histogram.commits, 2);
+    }
+}
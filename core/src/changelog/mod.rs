@@ -0,0 +1,236 @@
+use serde::Serialize;
+
+use crate::models::{Changelog, ChangelogEntry, ChangelogError, ChangelogFormat};
+
+mod template;
+
+/// The categories of <https://keepachangelog.com> and the commit types listed in them.
+///
+/// Types that are not listed, such as `revert`, count as changes, other than the well-known
+/// ones that do not change the behaviour of the software.
+const CATEGORIES: &[(&str, &[&str])] =
+    &[("Added", &["feat"]), ("Changed", &[]), ("Fixed", &["fix"])];
+
+/// The index of the `Changed` category in `CATEGORIES`.
+const CHANGED: usize = 1;
+
+/// The well-known commit types that do not change the behaviour of the software, unless they
+/// are breaking, in which case they count as changes.
+const INTERNAL_TYPES: &[&str] = &["build", "chore", "ci", "docs", "style", "test"];
+
+#[derive(Serialize)]
+struct Category<'a> {
+    title: &'static str,
+    entries: Vec<&'a ChangelogEntry>,
+}
+
+/// The data available to changelog templates.
+#[derive(Serialize)]
+struct TemplateData<'a> {
+    #[serde(flatten)]
+    changelog: &'a Changelog,
+    /// The entries grouped into the categories of Keep a Changelog.
+    categories: Vec<Category<'a>>,
+}
+
+/// Renders a changelog in the given format, or with a custom handlebars template.
+///
+/// Templates can use every field of the changelog, the entries grouped into the `categories`
+/// of Keep a Changelog, the `{{> entry}}` partial rendering an entry as a Markdown list item
+/// and the `{{json value}}` helper.
+pub(crate) fn render(
+    changelog: &Changelog, format: ChangelogFormat, template: Option<&str>,
+) -> Result<String, ChangelogError> {
+    let handlebars = template::setup_templates(format, template)?;
+    let data = TemplateData {
+        changelog,
+        categories: categories(changelog),
+    };
+    Ok(handlebars.render("changelog", &data)?)
+}
+
+fn categories(changelog: &Changelog) -> Vec<Category<'_>> {
+    let category = |commit_type: &str| {
+        CATEGORIES
+            .iter()
+            .position(|(_, types)| types.contains(&commit_type))
+            .or_else(|| (!INTERNAL_TYPES.contains(&commit_type)).then_some(CHANGED))
+    };
+
+    let mut categories: Vec<Category> = CATEGORIES
+        .iter()
+        .map(|(title, _)| Category {
+            title,
+            entries: Vec::new(),
+        })
+        .collect();
+    for section in &changelog.sections {
+        let index = category(&section.commit_type);
+        for entry in section.scopes.iter().flat_map(|scope| &scope.entries) {
+            if let Some(index) = index.or_else(|| entry.breaking.then_some(CHANGED)) {
+                categories[index].entries.push(entry);
+            }
+        }
+    }
+    categories.retain(|category| !category.entries.is_empty());
+    categories
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{ChangelogScope, ChangelogSection};
+
+    use super::*;
+
+    fn entry(commit_type: &str, scope: Option<&str>, description: &str) -> ChangelogEntry {
+        ChangelogEntry {
+            commit: "0123456789abcdef".to_string(),
+            short_commit: "0123456".to_string(),
+            commit_url: Some("https://github.com/acme/app/commit/0123456789abcdef".to_string()),
+            commit_type: commit_type.to_string(),
+            scope: scope.map(str::to_string),
+            description: description.to_string(),
+            breaking: false,
+            breaking_change: None,
+            author: "Jane Doe".to_string(),
+            email: "jane@example.com".to_string(),
+            author_url: None,
+            pull_request: None,
+            pull_request_url: None,
+        }
+    }
+
+    fn changelog() -> Changelog {
+        let mut breaking = entry("feat", Some("api"), "drop the v1 API");
+        breaking.breaking = true;
+        breaking.breaking_change = Some("the v1 routes are gone".to_string());
+        breaking.pull_request = Some(12);
+        breaking.pull_request_url = Some("https://github.com/acme/app/pull/12".to_string());
+
+        let section =
+            |commit_type: &str, title: &str, entries: Vec<ChangelogEntry>| ChangelogSection {
+                commit_type: commit_type.to_string(),
+                title: title.to_string(),
+                scopes: vec![ChangelogScope {
+                    scope: entries[0].scope.clone(),
+                    entries,
+                }],
+            };
+        Changelog {
+            version: Some("2.0.0".to_string()),
+            date: "2024-07-18".to_string(),
+            start_commit: "v1.4.0".to_string(),
+            end_commit: "main".to_string(),
+            sections: vec![
+                section("feat", "Features", vec![breaking.clone()]),
+                section("fix", "Bug Fixes", vec![entry("fix", None, "handle empty body")]),
+                section("docs", "Documentation", vec![entry("docs", None, "describe setup")]),
+            ],
+            breaking_changes: vec![breaking],
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let rendered = render(&changelog(), ChangelogFormat::Markdown, None).unwrap();
+        assert_eq!(
+            rendered,
+            "\
+## 2.0.0 (2024-07-18)
+
+### ⚠ BREAKING CHANGES
+
+- **api:** the v1 routes are gone
+
+### Features
+
+- **api:** drop the v1 API ([0123456](https://github.com/acme/app/commit/0123456789abcdef), [#12](https://github.com/acme/app/pull/12)) by Jane Doe
+
+### Bug Fixes
+
+- handle empty body ([0123456](https://github.com/acme/app/commit/0123456789abcdef)) by Jane Doe
+
+### Documentation
+
+- describe setup ([0123456](https://github.com/acme/app/commit/0123456789abcdef)) by Jane Doe
+"
+        );
+    }
+
+    #[test]
+    fn test_render_keep_a_changelog() {
+        let rendered = render(&changelog(), ChangelogFormat::KeepAChangelog, None).unwrap();
+        assert_eq!(
+            rendered,
+            "\
+## [2.0.0] - 2024-07-18
+
+### Added
+
+- **BREAKING:** **api:** drop the v1 API ([0123456](https://github.com/acme/app/commit/0123456789abcdef), [#12](https://github.com/acme/app/pull/12)) by Jane Doe
+
+### Fixed
+
+- handle empty body ([0123456](https://github.com/acme/app/commit/0123456789abcdef)) by Jane Doe
+"
+        );
+    }
+
+    #[test]
+    fn test_keep_a_changelog_lists_breaking_internal_changes() {
+        let mut breaking = entry("build", None, "require Rust 1.80");
+        breaking.breaking = true;
+        let changelog = Changelog {
+            sections: vec![ChangelogSection {
+                commit_type: "build".to_string(),
+                title: "Build System".to_string(),
+                scopes: vec![ChangelogScope {
+                    scope: None,
+                    entries: vec![breaking.clone(), entry("build", None, "bump serde")],
+                }],
+            }],
+            breaking_changes: vec![breaking],
+            ..changelog()
+        };
+
+        let categories = categories(&changelog);
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].title, "Changed");
+        assert_eq!(categories[0].entries.len(), 1);
+        assert_eq!(categories[0].entries[0].description, "require Rust 1.80");
+    }
+
+    #[test]
+    fn test_keep_a_changelog_lists_reverts_as_changes() {
+        let changelog = Changelog {
+            sections: vec![ChangelogSection {
+                commit_type: "revert".to_string(),
+                title: "Reverts".to_string(),
+                scopes: vec![ChangelogScope {
+                    scope: None,
+                    entries: vec![entry("revert", None, "restore the v1 API")],
+                }],
+            }],
+            ..changelog()
+        };
+
+        let categories = categories(&changelog);
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].title, "Changed");
+    }
+
+    #[test]
+    fn test_render_json_and_custom_templates() {
+        let rendered = render(&changelog(), ChangelogFormat::Json, None).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(json["version"], "2.0.0");
+        assert_eq!(json["categories"][1]["title"], "Fixed");
+
+        let template = "{{#each sections}}{{commit_type}} {{/each}}";
+        let rendered = render(&changelog(), ChangelogFormat::Markdown, Some(template)).unwrap();
+        assert_eq!(rendered, "feat fix docs ");
+
+        let error = render(&changelog(), ChangelogFormat::Markdown, Some("{{#each}")).unwrap_err();
+        assert!(matches!(error, ChangelogError::Template(_)));
+    }
+}
@@ -0,0 +1,66 @@
+use handlebars::{handlebars_helper, Handlebars};
+
+use crate::models::{ChangelogError, ChangelogFormat};
+
+/// A single entry, shared by the built-in templates and available to custom ones.
+const ENTRY: &str = "{{#if scope}}**{{scope}}:** {{/if}}{{description}} \
+({{#if commit_url}}[{{short_commit}}]({{commit_url}}){{else}}{{short_commit}}{{/if}}\
+{{#if pull_request}}, {{#if pull_request_url}}[#{{pull_request}}]({{pull_request_url}})\
+{{else}}#{{pull_request}}{{/if}}{{/if}}) \
+by {{#if author_url}}[{{author}}]({{author_url}}){{else}}{{author}}{{/if}}";
+
+const MARKDOWN: &str = r#"## {{#if version}}{{version}}{{else}}Unreleased{{/if}} ({{date}})
+{{#if breaking_changes}}
+
+### ⚠ BREAKING CHANGES
+
+{{#each breaking_changes}}
+- {{#if scope}}**{{scope}}:** {{/if}}{{#if breaking_change}}{{breaking_change}}{{else}}{{description}}{{/if}}
+{{/each}}
+{{/if}}
+{{#each sections}}
+
+### {{title}}
+
+{{#each scopes}}
+{{#each entries}}
+- {{> entry}}
+{{/each}}
+{{/each}}
+{{/each}}
+"#;
+
+const KEEP_A_CHANGELOG: &str = r#"## {{#if version}}[{{version}}] - {{date}}{{else}}[Unreleased]{{/if}}
+{{#each categories}}
+
+### {{title}}
+
+{{#each entries}}
+- {{#if breaking}}**BREAKING:** {{/if}}{{> entry}}
+{{/each}}
+{{/each}}
+"#;
+
+const JSON: &str = "{{json this}}\n";
+
+handlebars_helper!(json: |value: Json| {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+});
+
+/// Sets up the template for the given format, or the custom one when given.
+pub(crate) fn setup_templates(
+    format: ChangelogFormat, template: Option<&str>,
+) -> Result<Handlebars<'static>, ChangelogError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_helper("json", Box::new(json));
+    handlebars.register_partial("entry", ENTRY)?;
+
+    let template = template.unwrap_or(match format {
+        ChangelogFormat::Markdown => MARKDOWN,
+        ChangelogFormat::Json => JSON,
+        ChangelogFormat::KeepAChangelog => KEEP_A_CHANGELOG,
+    });
+    handlebars.register_template_string("changelog", template)?;
+    Ok(handlebars)
+}
@@ -1,9 +1,13 @@
 use std::fmt;
 
+mod parser;
 pub(crate) mod template;
 
-/// Represents the types of commits recognized by the Conventional Commits 1.0.0-beta.2 specification.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+/// Represents the types of commits recognized by the Conventional Commits 1.0.0 specification.
+///
+/// The specification allows any noun as a type, so types other than the well-known ones are kept
+/// as they were written.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 enum CommitType {
     Feat,
@@ -15,13 +19,16 @@ enum CommitType {
     Perf,
     Test,
     Build,
+    Ci,
     Ops,
+    Revert,
     Improvement,
+    Other(String),
 }
 
 impl CommitType {
     /// Retrieves the string representation of the commit type.
-    fn as_str(&self) -> &'static str {
+    fn as_str(&self) -> &str {
         match self {
             CommitType::Feat => "feat",
             CommitType::Fix => "fix",
@@ -32,70 +39,148 @@ impl CommitType {
             CommitType::Perf => "perf",
             CommitType::Test => "test",
             CommitType::Build => "build",
+            CommitType::Ci => "ci",
             CommitType::Ops => "ops",
+            CommitType::Revert => "revert",
             CommitType::Improvement => "improvement",
+            CommitType::Other(commit_type) => commit_type,
         }
     }
 
-    /// Returns the corresponding emoji for each commit type.
-    fn emoji(&self) -> Emoji {
+    /// Returns the corresponding emoji for each well-known commit type.
+    fn emoji(&self) -> Option<Emoji> {
         match self {
-            CommitType::Feat => Emoji::Feat,
-            CommitType::Fix => Emoji::Fix,
-            CommitType::Docs => Emoji::Docs,
-            CommitType::Style => Emoji::Style,
-            CommitType::Refactor => Emoji::Refactor,
-            CommitType::Perf => Emoji::Perf,
-            CommitType::Test => Emoji::Test,
-            CommitType::Build => Emoji::Build,
-            CommitType::Chore => Emoji::Chore,
-            // CommitType::Revert => Emoji::Revert,
-            CommitType::Improvement => Emoji::Feat,
-            CommitType::Ops => Emoji::Ci,
+            CommitType::Feat => Some(Emoji::Feat),
+            CommitType::Fix => Some(Emoji::Fix),
+            CommitType::Docs => Some(Emoji::Docs),
+            CommitType::Style => Some(Emoji::Style),
+            CommitType::Refactor => Some(Emoji::Refactor),
+            CommitType::Perf => Some(Emoji::Perf),
+            CommitType::Test => Some(Emoji::Test),
+            CommitType::Build => Some(Emoji::Build),
+            CommitType::Chore => Some(Emoji::Chore),
+            CommitType::Revert => Some(Emoji::Revert),
+            CommitType::Improvement => Some(Emoji::Feat),
+            CommitType::Ci | CommitType::Ops => Some(Emoji::Ci),
+            CommitType::Other(_) => None,
+        }
+    }
+}
+
+impl From<&str> for CommitType {
+    /// Parses a commit type, ignoring case as the specification requires.
+    fn from(commit_type: &str) -> Self {
+        match commit_type.to_lowercase().as_str() {
+            "feat" => CommitType::Feat,
+            "fix" => CommitType::Fix,
+            "chore" => CommitType::Chore,
+            "docs" => CommitType::Docs,
+            "style" => CommitType::Style,
+            "refactor" => CommitType::Refactor,
+            "perf" => CommitType::Perf,
+            "test" => CommitType::Test,
+            "build" => CommitType::Build,
+            "ci" => CommitType::Ci,
+            "ops" => CommitType::Ops,
+            "revert" => CommitType::Revert,
+            "improvement" => CommitType::Improvement,
+            _ => CommitType::Other(commit_type.to_string()),
+        }
+    }
+}
+
+/// The separator between a footer's token and its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+enum FooterSeparator {
+    /// `Token: value`
+    Colon,
+    /// `Token #value`, as used for issue references.
+    Hash,
+}
+
+/// A footer, also known as a trailer, such as `Reviewed-by: Z` or `Refs #133`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Footer {
+    token: String,
+    separator: FooterSeparator,
+    value: String,
+}
+
+impl fmt::Display for Footer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.separator {
+            FooterSeparator::Colon => write!(f, "{}: {}", self.token, self.value),
+            FooterSeparator::Hash => write!(f, "{} #{}", self.token, self.value),
         }
     }
 }
 
-/// Represents a conventional commit, adhering to the Conventional Commits 1.0.0-beta.2 specification.
+/// Represents a conventional commit, adhering to the Conventional Commits 1.0.0 specification.
 /// This structure encapsulates all possible elements of a conventional commit message.
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct Commit {
     /// The type of commit, indicating the nature of the changes.
     commit_type: CommitType,
     /// Optional scope providing additional context on the area of the codebase affected.
     scope: Option<String>,
+    /// Whether the header carries the `!` marker of a breaking change.
+    breaking: bool,
     /// A concise description of the changes, following the type and scope.
     description: String,
     /// An optional detailed description of the changes, providing additional context.
     body: Option<String>,
-    /// An optional footer containing additional metadata, such as issue references or breaking changes.
-    footer: Option<String>,
-    /// Indicates if the commit introduces breaking changes, correlating with MAJOR in semantic versioning.
+    /// The description of a `BREAKING CHANGE` footer, correlating with MAJOR in semantic versioning.
     breaking_change: Option<String>,
+    /// Other footers containing additional metadata, such as issue references or reviewers.
+    footers: Vec<Footer>,
     /// Optional bool for emoji
     use_emoji: bool,
 }
 
 impl Commit {
-    fn new(
-        commit_type: CommitType,
-        scope: Option<String>,
-        description: String,
-        body: Option<String>,
-        footer: Option<String>,
-        breaking_change: Option<String>,
-        use_emoji: bool, // New parameter to specify emoji usage
-    ) -> Self {
+    fn new(commit_type: CommitType, description: String) -> Self {
         Commit {
             commit_type,
-            scope,
+            scope: None,
+            breaking: false,
             description,
-            body,
-            footer,
-            breaking_change,
-            use_emoji,
+            body: None,
+            breaking_change: None,
+            footers: Vec::new(),
+            use_emoji: false,
         }
     }
+
+    /// Returns the type of the commit, in lowercase for the well-known types.
+    pub fn commit_type(&self) -> &str {
+        self.commit_type.as_str()
+    }
+
+    /// Returns the scope of the commit, if any.
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Returns the description of the commit, without the emoji of its type.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns whether the header carries the `!` marker of a breaking change.
+    pub fn has_breaking_marker(&self) -> bool {
+        self.breaking
+    }
+
+    /// Returns whether the commit introduces a breaking change, with the `!` marker or a
+    /// `BREAKING CHANGE` footer.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking || self.breaking_change.is_some()
+    }
+
+    /// Returns the description of the `BREAKING CHANGE` footer, if any.
+    pub fn breaking_change(&self) -> Option<&str> {
+        self.breaking_change.as_deref()
+    }
 }
 
 /// Represents emoji symbols associated with different types of conventional commits.
@@ -147,35 +232,72 @@ impl fmt::Display for Commit {
 mod tests {
     use super::*;
 
+    impl Commit {
+        fn with_scope(mut self, scope: String) -> Self {
+            self.scope = Some(scope);
+            self
+        }
+
+        /// Marks the commit as breaking with `!` in the header.
+        fn with_breaking_marker(mut self) -> Self {
+            self.breaking = true;
+            self
+        }
+
+        fn with_body(mut self, body: String) -> Self {
+            self.body = Some(body);
+            self
+        }
+
+        /// Adds a `BREAKING CHANGE` footer describing the breaking change.
+        fn with_breaking_change(mut self, breaking_change: String) -> Self {
+            self.breaking_change = Some(breaking_change);
+            self
+        }
+
+        /// Adds a `token: value` footer.
+        fn with_footer(mut self, token: String, value: String) -> Self {
+            self.footers.push(Footer {
+                token,
+                separator: FooterSeparator::Colon,
+                value,
+            });
+            self
+        }
+
+        /// Adds a `token #value` footer, such as `Refs #133`.
+        fn with_reference(mut self, token: String, value: String) -> Self {
+            self.footers.push(Footer {
+                token,
+                separator: FooterSeparator::Hash,
+                value,
+            });
+            self
+        }
+
+        /// Appends the emoji of the commit type, if it has one, to the description.
+        fn with_emoji(mut self, use_emoji: bool) -> Self {
+            self.use_emoji = use_emoji;
+            self
+        }
+    }
+
     /// Tests a simple feature commit without any optional fields.
     #[test]
     fn test_feature_commit() {
-        let commit = Commit::new(
-            CommitType::Feat,
-            None,
-            "Add new user login API".to_string(),
-            None,
-            None,
-            None,
-            false, // Disable emoji
-        );
+        let commit = Commit::new(CommitType::Feat, "Add new user login API".to_string());
         assert_eq!(commit.to_string(), "feat: Add new user login API");
     }
 
     /// Tests a bug fix commit with a scope and body.
     #[test]
     fn test_bug_fix_commit() {
-        let commit = Commit::new(
-            CommitType::Fix,
-            Some("auth".to_string()),
-            "Fix CSRF vulnerability".to_string(),
-            Some("Adds token validation on user session".to_string()),
-            None,
-            None,
-            false, // Disable emoji
-        );
+        let commit = Commit::new(CommitType::Fix, "Fix CSRF vulnerability".to_string())
+            .with_scope("auth".to_string())
+            .with_body("Adds token validation on user session".to_string());
         let expected_message = "\
 fix(auth): Fix CSRF vulnerability
+
 Adds token validation on user session";
         assert_eq!(commit.to_string(), expected_message);
     }
@@ -183,38 +305,74 @@ Adds token validation on user session";
     /// Tests a commit that introduces a breaking change.
     #[test]
     fn test_breaking_change_commit() {
-        let commit = Commit::new(
-            CommitType::Refactor,
-            Some("database".to_string()),
-            "Change database schema for users".to_string(),
-            None,
-            Some("Related to issue #123".to_string()),
-            Some("Change column 'name' to 'username'".to_string()),
-            false, // Disable emoji
-        );
+        let commit =
+            Commit::new(CommitType::Refactor, "Change database schema for users".to_string())
+                .with_scope("database".to_string())
+                .with_body("Related to issue #123".to_string())
+                .with_breaking_change("Change column 'name' to 'username'".to_string());
         let expected_message = "\
 refactor(database): Change database schema for users
+
+Related to issue #123
+
+BREAKING CHANGE: Change column 'name' to 'username'";
+        assert_eq!(commit.to_string(), expected_message);
+    }
+
+    /// Tests a breaking change marked with `!` in the header, with an issue reference.
+    #[test]
+    fn test_breaking_marker_commit() {
+        let commit =
+            Commit::new(CommitType::Refactor, "Change database schema for users".to_string())
+                .with_scope("database".to_string())
+                .with_breaking_marker()
+                .with_breaking_change("Change column 'name' to 'username'".to_string())
+                .with_reference("Refs".to_string(), "123".to_string());
+        let expected_message = "\
+refactor(database)!: Change database schema for users
+
 BREAKING CHANGE: Change column 'name' to 'username'
-Related to issue #123";
+Refs #123";
         assert_eq!(commit.to_string(), expected_message);
     }
 
     /// Tests a commit with all fields utilized.
     #[test]
     fn test_full_commit() {
-        let commit = Commit::new(
-            CommitType::Perf,
-            Some("rendering".to_string()),
-            "Improve shadow rendering performance".to_string(),
-            Some("Optimizes shadow rendering for mobile devices".to_string()),
-            Some("See PR #456 for more details".to_string()),
-            None,
-            false, // Disable emoji
-        );
+        let commit =
+            Commit::new(CommitType::Perf, "Improve shadow rendering performance".to_string())
+                .with_scope("rendering".to_string())
+                .with_body("Optimizes shadow rendering for mobile devices".to_string())
+                .with_reference("Refs".to_string(), "456".to_string())
+                .with_emoji(false);
         let expected_message = "\
 perf(rendering): Improve shadow rendering performance
+
 Optimizes shadow rendering for mobile devices
-See PR #456 for more details";
+
+Refs #456";
         assert_eq!(commit.to_string(), expected_message);
     }
+
+    /// Tests a commit with several `token: value` footers.
+    #[test]
+    fn test_footer_commit() {
+        let commit = Commit::new(CommitType::Docs, "Document the changelog API".to_string())
+            .with_footer("See-also".to_string(), "PR 456 for more details".to_string())
+            .with_footer("Reviewed-by".to_string(), "Z".to_string());
+        let expected_message = "\
+docs: Document the changelog API
+
+See-also: PR 456 for more details
+Reviewed-by: Z";
+        assert_eq!(commit.to_string(), expected_message);
+    }
+
+    /// Tests a commit with the emoji of its type appended to the description.
+    #[test]
+    fn test_emoji_commit() {
+        let commit =
+            Commit::new(CommitType::Perf, "Cache compiled shaders".to_string()).with_emoji(true);
+        assert_eq!(commit.to_string(), "perf: Cache compiled shaders 🚀");
+    }
 }
@@ -0,0 +1,455 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::{Commit, CommitType, Footer, FooterSeparator};
+
+/// The footer tokens that introduce a breaking change, which are synonyms.
+const BREAKING_CHANGE_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// An invalid commit message, with the 1-based line and column at which parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{kind} at line {line}, column {column}")]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The reasons a commit message is not a valid conventional commit.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseErrorKind {
+    #[error("the commit message is empty")]
+    Empty,
+    #[error("expected {expected}, found {found}")]
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("the scope is empty")]
+    EmptyScope,
+    #[error("the description is empty")]
+    EmptyDescription,
+    #[error("expected a blank line between the header and the body")]
+    MissingBlankLine,
+    #[error("the `{0}` footer has no value")]
+    EmptyFooter(String),
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, line: usize, column: usize) -> Self {
+        Self { kind, line, column }
+    }
+}
+
+/// The parts of a `<type>[(<scope>)][!]: <description>` header.
+struct Header {
+    commit_type: String,
+    scope: Option<String>,
+    breaking: bool,
+    description: String,
+}
+
+impl FromStr for Commit {
+    type Err = ParseError;
+
+    /// Parses a commit message following the Conventional Commits 1.0.0 specification.
+    ///
+    /// The body starts after the blank line following the header. Footers are taken from the
+    /// last paragraph of the message when it starts with a `Token: value` or `Token #value`
+    /// line, and later lines that do not start a footer continue the value of the previous one.
+    /// The emoji of the commit type at the end of the description, as rendered, is recognized.
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = message.trim_end().lines().collect();
+        let Some(header) = lines.first() else {
+            return Err(ParseError::new(ParseErrorKind::Empty, 1, 1));
+        };
+
+        let header = parse_header(header)?;
+        let mut commit =
+            Commit::new(CommitType::from(header.commit_type.as_str()), header.description);
+        commit.scope = header.scope;
+        commit.breaking = header.breaking;
+        if let Some(emoji) = commit.commit_type.emoji() {
+            if let Some(description) = commit
+                .description
+                .strip_suffix(&format!(" {}", emoji.as_char()))
+            {
+                commit.description = description.to_string();
+                commit.use_emoji = true;
+            }
+        }
+
+        if lines.len() == 1 {
+            return Ok(commit);
+        }
+        if !lines[1].trim().is_empty() {
+            return Err(ParseError::new(ParseErrorKind::MissingBlankLine, 2, 1));
+        }
+        let Some(start) = (1..lines.len()).find(|&i| !lines[i].trim().is_empty()) else {
+            return Ok(commit);
+        };
+
+        let last_paragraph = (start..lines.len())
+            .rev()
+            .find(|&i| i == start || lines[i - 1].trim().is_empty())
+            .unwrap_or(start);
+        let footer_start = if split_footer(lines[last_paragraph]).is_some() {
+            last_paragraph
+        } else {
+            lines.len()
+        };
+
+        let body = lines[start..footer_start].join("\n");
+        let body = body.trim_end();
+        if !body.is_empty() {
+            commit.body = Some(body.to_string());
+        }
+
+        for (line, footer) in parse_footers(&lines[footer_start..], footer_start + 1) {
+            if footer.value.trim().is_empty() {
+                // Points at where the value should start, right after the separator.
+                let column = footer.token.chars().count() + 3;
+                return Err(ParseError::new(
+                    ParseErrorKind::EmptyFooter(footer.token),
+                    line,
+                    column,
+                ));
+            }
+            if BREAKING_CHANGE_TOKENS.contains(&footer.token.as_str())
+                && commit.breaking_change.is_none()
+            {
+                commit.breaking_change = Some(footer.value);
+            } else {
+                commit.footers.push(footer);
+            }
+        }
+
+        Ok(commit)
+    }
+}
+
+impl Commit {
+    /// Parses the message of a commit, falling back to the body of merge commits whose own
+    /// header is not conventional, like the ones GitHub and GitLab write.
+    pub fn from_commit_message(message: &str, merge: bool) -> Option<Self> {
+        message.parse().ok().or_else(|| {
+            let (_, body) = message.split_once("\n\n").filter(|_| merge)?;
+            body.trim_start().parse().ok()
+        })
+    }
+}
+
+fn parse_header(header: &str) -> Result<Header, ParseError> {
+    let chars: Vec<char> = header.chars().collect();
+    let error = |kind, pos: usize| ParseError::new(kind, 1, pos + 1);
+    let unexpected = |expected, pos: usize| {
+        let found = chars
+            .get(pos)
+            .map_or_else(|| "end of line".to_string(), |c| format!("{:?}", c));
+        error(ParseErrorKind::Unexpected { expected, found }, pos)
+    };
+
+    let type_end = chars
+        .iter()
+        .position(|c| !(c.is_alphanumeric() || *c == '-' || *c == '_'))
+        .unwrap_or(chars.len());
+    if type_end == 0 {
+        return Err(unexpected("a commit type", 0));
+    }
+    let commit_type = chars[..type_end].iter().collect();
+    let mut pos = type_end;
+
+    let mut scope = None;
+    if chars.get(pos) == Some(&'(') {
+        let start = pos + 1;
+        let end = chars[start..]
+            .iter()
+            .position(|c| *c == ')')
+            .map(|offset| start + offset)
+            .ok_or_else(|| unexpected("')'", chars.len()))?;
+        if end == start {
+            return Err(error(ParseErrorKind::EmptyScope, end));
+        }
+        scope = Some(chars[start..end].iter().collect());
+        pos = end + 1;
+    }
+
+    let breaking = chars.get(pos) == Some(&'!');
+    if breaking {
+        pos += 1;
+    }
+
+    if chars.get(pos) != Some(&':') {
+        let expected = match (scope.is_some(), breaking) {
+            (_, true) => "':'",
+            (true, false) => "'!' or ':'",
+            (false, false) => "'(', '!' or ':'",
+        };
+        return Err(unexpected(expected, pos));
+    }
+    pos += 1;
+    if chars.get(pos) != Some(&' ') {
+        return Err(unexpected("a space after ':'", pos));
+    }
+    pos += 1;
+
+    let description = chars[pos..].iter().collect::<String>().trim().to_string();
+    if description.is_empty() {
+        return Err(error(ParseErrorKind::EmptyDescription, pos));
+    }
+
+    Ok(Header {
+        commit_type,
+        scope,
+        breaking,
+        description,
+    })
+}
+
+/// Parses a footer block, returning each footer with the 1-based line it starts on.
+///
+/// The first line of the block must start a footer. Trailing whitespace is dropped from values,
+/// as it is from the last line of the message, so that a value reads the same whatever footer
+/// is rendered last.
+fn parse_footers(lines: &[&str], first_line: usize) -> Vec<(usize, Footer)> {
+    let mut footers: Vec<(usize, Footer)> = Vec::new();
+    for (offset, line) in lines.iter().enumerate() {
+        if let Some((token, separator, value)) = split_footer(line) {
+            let footer = Footer {
+                token: token.to_string(),
+                separator,
+                value: value.to_string(),
+            };
+            footers.push((first_line + offset, footer));
+        } else if let Some((_, footer)) = footers.last_mut() {
+            footer.value.push('\n');
+            footer.value.push_str(line);
+        }
+    }
+    for (_, footer) in &mut footers {
+        footer.value.truncate(footer.value.trim_end().len());
+    }
+    footers
+}
+
+/// Splits a line that starts a footer into its token, separator and value.
+fn split_footer(line: &str) -> Option<(&str, FooterSeparator, &str)> {
+    let (token, rest) = match BREAKING_CHANGE_TOKENS
+        .iter()
+        .find(|token| line.starts_with(*token))
+    {
+        Some(token) => line.split_at(token.len()),
+        None => {
+            let end = line
+                .find(|c: char| !(c.is_alphanumeric() || c == '-'))
+                .unwrap_or(line.len());
+            if end == 0 {
+                return None;
+            }
+            line.split_at(end)
+        }
+    };
+
+    if rest == ":" {
+        Some((token, FooterSeparator::Colon, ""))
+    } else if let Some(value) = rest.strip_prefix(": ") {
+        Some((token, FooterSeparator::Colon, value))
+    } else if let Some(value) = rest.strip_prefix(" #") {
+        Some((token, FooterSeparator::Hash, value))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_full_message() {
+        let message = "\
+feat(api)!: send an email to the customer when a product is shipped
+
+Introduce a request id and a reference to latest request.
+
+Remove timeouts which were used to mitigate the racing issue.
+
+Reviewed-by: Z
+BREAKING CHANGE: drop support for Node 6,
+which is end of life
+Refs #133
+";
+        let commit: Commit = message.parse().unwrap();
+
+        assert_eq!(commit.commit_type, CommitType::Feat);
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert!(commit.breaking);
+        assert_eq!(commit.description, "send an email to the customer when a product is shipped");
+        assert_eq!(
+            commit.body.as_deref(),
+            Some(
+                "Introduce a request id and a reference to latest request.\n\n\
+                 Remove timeouts which were used to mitigate the racing issue."
+            )
+        );
+        assert_eq!(
+            commit.breaking_change.as_deref(),
+            Some("drop support for Node 6,\nwhich is end of life")
+        );
+        assert_eq!(
+            commit.footers,
+            [
+                Footer {
+                    token: "Reviewed-by".to_string(),
+                    separator: FooterSeparator::Colon,
+                    value: "Z".to_string(),
+                },
+                Footer {
+                    token: "Refs".to_string(),
+                    separator: FooterSeparator::Hash,
+                    value: "133".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_trims_footer_values() {
+        let commit: Commit = "fix: typo\n\nAcked-by: Z \nBREAKING CHANGE: drop v1"
+            .parse()
+            .unwrap();
+        assert_eq!(commit.footers[0].value, "Z");
+        let reparsed: Commit = commit.to_string().parse().unwrap();
+        assert_eq!(reparsed, commit);
+    }
+
+    #[test]
+    fn test_parse_is_case_insensitive_for_types() {
+        let commit: Commit = "Docs: correct spelling of CHANGELOG".parse().unwrap();
+        assert_eq!(commit.commit_type, CommitType::Docs);
+
+        let commit: Commit = "wip: try things out".parse().unwrap();
+        assert_eq!(commit.commit_type, CommitType::Other("wip".to_string()));
+    }
+
+    #[test]
+    fn test_parse_errors_have_positions() {
+        let error = |message: &str| message.parse::<Commit>().unwrap_err();
+
+        assert_eq!(error(""), ParseError::new(ParseErrorKind::Empty, 1, 1));
+        assert_eq!(
+            error("feat add login"),
+            ParseError::new(
+                ParseErrorKind::Unexpected {
+                    expected: "'(', '!' or ':'",
+                    found: "' '".to_string(),
+                },
+                1,
+                5
+            )
+        );
+        assert_eq!(error("fix(): typo"), ParseError::new(ParseErrorKind::EmptyScope, 1, 5));
+        assert_eq!(
+            error("fix(parser: typo").to_string(),
+            "expected ')', found end of line at line 1, column 17"
+        );
+        assert_eq!(
+            error("fix:typo").to_string(),
+            "expected a space after ':', found 't' at line 1, column 5"
+        );
+        assert_eq!(error("fix: \n\nbody"), ParseError::new(ParseErrorKind::EmptyDescription, 1, 6));
+        assert_eq!(
+            error("fix: typo\nbody"),
+            ParseError::new(ParseErrorKind::MissingBlankLine, 2, 1)
+        );
+        assert_eq!(
+            error("fix: typo\n\nbody\n\nRefs: 1\nBREAKING CHANGE:"),
+            ParseError::new(ParseErrorKind::EmptyFooter("BREAKING CHANGE".to_string()), 6, 18)
+        );
+    }
+
+    fn commit_strategy() -> impl Strategy<Value = Commit> {
+        let line = "[a-z]{1,8}( [a-z0-9,.]{1,8}){1,6}";
+        let paragraph = prop::collection::vec(line, 1..3).prop_map(|lines| lines.join("\n"));
+        let body = prop::collection::vec(paragraph, 1..3).prop_map(|p| p.join("\n\n"));
+        let footer = (
+            "[A-Z][a-z]{1,6}(-[a-z]{1,6})?",
+            prop_oneof![
+                "[a-z0-9]{1,6}( [a-z0-9]{1,6}){0,4}"
+                    .prop_map(|value| (FooterSeparator::Colon, value)),
+                "[0-9]{1,5}".prop_map(|value| (FooterSeparator::Hash, value)),
+            ],
+        )
+            .prop_map(|(token, (separator, value))| Footer {
+                token,
+                separator,
+                value,
+            });
+
+        (
+            "[a-zA-Z][a-zA-Z0-9-]{0,8}",
+            prop::option::of("[a-z][a-z0-9_-]{0,10}"),
+            any::<bool>(),
+            "[A-Za-z0-9]([A-Za-z0-9 ,.']{0,40}[A-Za-z0-9.])?",
+            prop::option::of(body),
+            prop::option::of("[a-z0-9]{1,8}( [a-z0-9]{1,8}){0,6}"),
+            prop::collection::vec(footer, 0..3),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(
+                    commit_type,
+                    scope,
+                    breaking,
+                    description,
+                    body,
+                    breaking_change,
+                    footers,
+                    emoji,
+                )| {
+                    let commit_type = CommitType::from(commit_type.as_str());
+                    let use_emoji = emoji && commit_type.emoji().is_some();
+                    Commit {
+                        commit_type,
+                        scope,
+                        breaking,
+                        description,
+                        body,
+                        breaking_change,
+                        footers,
+                        use_emoji,
+                    }
+                },
+            )
+    }
+
+    fn message_strategy() -> impl Strategy<Value = String> {
+        let line = prop_oneof![
+            "(feat|fix|Docs)(\\([a-z]*\\))?!?: ?[a-z #]*",
+            Just(String::new()),
+            "[A-Z][a-z-]{0,6}(: | #)?[a-z0-9 ]*",
+            "BREAKING(-| )CHANGE:( [a-z ]*)?",
+            "\\PC{0,20}",
+        ];
+        prop::collection::vec(line, 1..8).prop_map(|lines| lines.join("\n"))
+    }
+
+    proptest! {
+        #[test]
+        fn test_render_then_parse_round_trips(commit in commit_strategy()) {
+            let parsed: Commit = commit.to_string().parse().unwrap();
+            prop_assert_eq!(parsed, commit);
+        }
+
+        #[test]
+        fn test_parse_then_render_is_stable(message in message_strategy()) {
+            if let Ok(commit) = message.parse::<Commit>() {
+                let reparsed: Commit = commit.to_string().parse().unwrap();
+                prop_assert_eq!(reparsed, commit);
+            }
+        }
+    }
+}
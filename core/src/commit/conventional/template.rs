@@ -7,25 +7,36 @@ pub(crate) fn setup_templates() -> handlebars::Handlebars<'static> {
     handlebars
         .register_template_string(
             "commit_message",
-            r#"{{type}}{{#if scope}}({{scope}}){{/if}}: {{description}}{{#if emoji}} {{emoji}}{{/if}}{{#if breaking_change}}
-BREAKING CHANGE: {{breaking_change}}{{/if}}{{#if body}}
-{{body}}{{/if}}{{#if footer}}
-{{footer}}{{/if}}"#,
+            r#"{{type}}{{#if scope}}({{scope}}){{/if}}{{#if breaking}}!{{/if}}: {{description}}{{#if emoji}} {{emoji}}{{/if}}{{#if body}}
+
+{{body}}{{/if}}{{#if footers}}
+
+{{#each footers}}{{#unless @first}}
+{{/unless}}{{this}}{{/each}}{{/if}}"#,
         )
         .expect("Template should compile without error");
     handlebars
 }
 
 /// Adjusted rendering to conditionally include emoji
+///
+/// A `BREAKING CHANGE` footer always comes first among the footers.
 pub fn render_commit_message(commit: &Commit, handlebars: &handlebars::Handlebars) -> String {
+    let footers: Vec<String> = commit
+        .breaking_change
+        .iter()
+        .map(|breaking_change| format!("BREAKING CHANGE: {}", breaking_change))
+        .chain(commit.footers.iter().map(ToString::to_string))
+        .collect();
+    let emoji = commit.commit_type.emoji().filter(|_| commit.use_emoji);
     let data = serde_json::json!({
-        "emoji": if commit.use_emoji { Some(commit.commit_type.emoji().as_char()) } else { None },
+        "emoji": emoji.map(|emoji| emoji.as_char()),
         "type": commit.commit_type.as_str(),
         "scope": commit.scope,
+        "breaking": commit.breaking,
         "description": commit.description,
         "body": commit.body,
-        "footer": commit.footer,
-        "breaking_change": commit.breaking_change,
+        "footers": footers,
     });
     handlebars
         .render("commit_message", &data)
@@ -1,11 +1,13 @@
 pub use library::*;
 
 pub(crate) mod analyzers;
+pub(crate) mod changelog;
 mod clients;
 pub(crate) mod commit;
 pub(crate) mod library;
 pub mod models;
 pub(crate) mod pull_request;
+pub(crate) mod release;
 pub(crate) mod repository;
 pub mod services;
 pub(crate) mod utils;
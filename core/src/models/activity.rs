@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// The calendar period that activity is bucketed by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Day,
+    /// Weeks starting on Monday.
+    #[default]
+    Week,
+    Month,
+}
+
+/// Represents the activity within a single calendar period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityBucket {
+    /// The first day of the period, in the series' timezone.
+    pub start: NaiveDate,
+    pub commits: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    pub active_authors: usize,
+}
+
+/// Represents activity over a range of commits as a series of consecutive calendar periods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySeries {
+    pub granularity: Granularity,
+    pub timezone: String,
+    pub buckets: Vec<ActivityBucket>,
+}
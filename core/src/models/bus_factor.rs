@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the share of lines an author contributed to a part of the repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorShare {
+    pub author: String,
+    pub email: String,
+    pub lines: usize,
+    /// The fraction of lines (between 0 and 1) attributed to the author.
+    pub share: f64,
+}
+
+/// Represents how knowledge of a directory is concentrated among its authors.
+///
+/// The `bus_factor` is the minimum number of authors whose departure would leave
+/// most of the directory's lines unowned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectoryKnowledge {
+    pub path: String,
+    pub lines: usize,
+    pub bus_factor: usize,
+    pub authors: Vec<AuthorShare>,
+    pub children: Vec<DirectoryKnowledge>,
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::HistoryMode;
+
+/// The formats a changelog can be rendered in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogFormat {
+    /// Markdown sections per commit type, in the style of `conventional-changelog`.
+    #[default]
+    Markdown,
+    /// The changelog data as JSON.
+    Json,
+    /// A release section following <https://keepachangelog.com>.
+    KeepAChangelog,
+}
+
+/// Options controlling how a changelog is generated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangelogOptions {
+    /// The format the changelog is rendered in.
+    #[serde(default)]
+    pub format: ChangelogFormat,
+    /// A handlebars template replacing the built-in one of the format.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// The version the changes are released in, or `None` when they are unreleased.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Which commits of the range are listed.
+    #[serde(default)]
+    pub history_mode: HistoryMode,
+    /// Whether to leave commits authored by bots out of the changelog.
+    #[serde(default)]
+    pub exclude_bots: bool,
+    /// Names or emails of additional accounts to treat as bots.
+    #[serde(default)]
+    pub bot_accounts: Vec<String>,
+    /// Maps author names or emails to the canonical email they belong to, on top of `.mailmap`.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+}
+
+/// Represents a conventional commit listed in a changelog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub commit: String,
+    pub short_commit: String,
+    pub commit_url: Option<String>,
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub description: String,
+    /// Whether the commit is marked as breaking or has a `BREAKING CHANGE` footer.
+    pub breaking: bool,
+    /// The description of the `BREAKING CHANGE` footer, if any.
+    pub breaking_change: Option<String>,
+    pub author: String,
+    pub email: String,
+    pub author_url: Option<String>,
+    /// The number of the pull request that landed the commit, if it can be told from the
+    /// commit message.
+    pub pull_request: Option<u64>,
+    pub pull_request_url: Option<String>,
+}
+
+/// Represents the entries of a changelog section that share a scope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogScope {
+    pub scope: Option<String>,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// Represents the entries of a changelog that share a commit type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogSection {
+    pub commit_type: String,
+    /// The heading of the section, such as `Bug Fixes` for `fix`.
+    pub title: String,
+    /// The entries grouped by scope, with the unscoped ones first.
+    pub scopes: Vec<ChangelogScope>,
+}
+
+/// Represents the conventional commits of a range, grouped for a changelog.
+///
+/// Commits whose messages are not conventional commits are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Changelog {
+    pub version: Option<String>,
+    /// The date of the end commit, as `YYYY-MM-DD`.
+    pub date: String,
+    pub start_commit: String,
+    pub end_commit: String,
+    /// The sections in a conventional order, starting with features and fixes.
+    pub sections: Vec<ChangelogSection>,
+    /// The breaking entries of every section, newest first.
+    pub breaking_changes: Vec<ChangelogEntry>,
+}
+
+/// The reasons a changelog cannot be rendered.
+#[derive(Debug, Error)]
+pub enum ChangelogError {
+    #[error("Invalid changelog template: {0}")]
+    Template(#[from] handlebars::TemplateError),
+    #[error("Failed to render the changelog: {0}")]
+    Render(#[from] handlebars::RenderError),
+}
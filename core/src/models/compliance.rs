@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+use crate::annotations::prelude::{Annotation, AnnotationLevel};
+
+/// The rules that commit messages are checked against, on top of the Conventional Commits
+/// format itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceRules {
+    /// The allowed commit types, compared ignoring case. Any type is allowed when empty.
+    #[serde(default = "ComplianceRules::default_allowed_types")]
+    pub allowed_types: Vec<String>,
+    /// Whether every commit must name a scope, as in `fix(parser): ...`.
+    #[serde(default)]
+    pub require_scope: bool,
+    /// The maximum number of characters in the subject line, if any.
+    #[serde(default = "ComplianceRules::default_max_subject_length")]
+    pub max_subject_length: Option<usize>,
+    /// Whether commits marked as breaking with `!` must also describe the change in a
+    /// `BREAKING CHANGE` footer.
+    #[serde(default)]
+    pub require_breaking_change_footer: bool,
+}
+
+impl Default for ComplianceRules {
+    fn default() -> Self {
+        Self {
+            allowed_types: Self::default_allowed_types(),
+            require_scope: false,
+            max_subject_length: Self::default_max_subject_length(),
+            require_breaking_change_footer: false,
+        }
+    }
+}
+
+impl ComplianceRules {
+    /// The types of the Angular convention, which most tooling recognizes.
+    fn default_allowed_types() -> Vec<String> {
+        [
+            "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style",
+            "test",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
+
+    fn default_max_subject_length() -> Option<usize> {
+        Some(72)
+    }
+}
+
+/// A rule that a commit message can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceRule {
+    /// The message is not a valid conventional commit.
+    Format,
+    /// The commit type is not one of the allowed types.
+    Type,
+    /// The commit does not name a scope.
+    Scope,
+    /// The subject line is too long.
+    SubjectLength,
+    /// The commit is marked as breaking but has no `BREAKING CHANGE` footer.
+    BreakingChangeFooter,
+}
+
+/// Represents a rule broken by a commit message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceViolation {
+    pub rule: ComplianceRule,
+    pub message: String,
+}
+
+/// Represents a commit whose message broke at least one rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitViolations {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    /// The first line of the commit message.
+    pub subject: String,
+    pub violations: Vec<ComplianceViolation>,
+}
+
+/// Represents how well the commit messages of a range follow the Conventional Commits rules.
+///
+/// Messages generated by git, such as those of merges, reverts and fixups, are not checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Compliance {
+    /// The number of commit messages that were checked.
+    pub checked: usize,
+    /// The number of checked commit messages that broke no rule.
+    pub compliant: usize,
+    /// The fraction of checked commit messages (between 0 and 1) that broke no rule, or 1 when
+    /// none were checked.
+    pub compliance_rate: f64,
+    /// The commits that broke at least one rule, newest first.
+    pub commits: Vec<CommitViolations>,
+}
+
+impl Compliance {
+    /// Converts every violation into an annotation that CI annotators can issue on a pull
+    /// request.
+    ///
+    /// Annotations are not tied to a line, and name the commit as their file.
+    pub fn annotations(&self, level: AnnotationLevel) -> Vec<Annotation> {
+        let level = &level;
+        self.commits
+            .iter()
+            .flat_map(|commit| {
+                let short_id = &commit.commit[..commit.commit.len().min(7)];
+                commit.violations.iter().map(move |violation| {
+                    Annotation::new_whole_file(
+                        commit.commit.clone(),
+                        format!("{} \"{}\": {}", short_id, commit.subject, violation.message),
+                        level.clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
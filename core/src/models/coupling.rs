@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents two files that tend to change in the same commits.
+///
+/// Reads as "when `path` changes, `coupled_path` changes too with probability `confidence`".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCoupling {
+    pub path: String,
+    pub coupled_path: String,
+    /// The number of commits that changed both files.
+    pub co_changes: usize,
+    /// The fraction of commits in the range (between 0 and 1) that changed both files.
+    pub support: f64,
+    /// The fraction of commits changing `path` (between 0 and 1) that also changed `coupled_path`.
+    pub confidence: f64,
+}
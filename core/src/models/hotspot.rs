@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Represents how often and how heavily a file changed over a range of commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub path: String,
+    /// The number of commits that touched the file.
+    pub changes: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    /// The total churn (additions + deletions) of the file.
+    pub churn: usize,
+    /// The number of distinct authors that touched the file.
+    pub authors: usize,
+    /// When the file was last touched within the range.
+    pub last_modified: DateTime<Utc>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the churn, files and authors of one language over a range of commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageBreakdown {
+    /// The language name, or `Other` for files that could not be classified.
+    pub language: String,
+    /// The number of commits that touched files in the language.
+    pub commits: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    /// The total churn (additions + deletions) in the language.
+    pub churn: usize,
+    /// The number of distinct files in the language that were touched.
+    pub files: usize,
+    /// The number of distinct authors that touched files in the language.
+    pub authors: usize,
+}
@@ -1,6 +1,14 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub use activity::{ActivityBucket, ActivitySeries, Granularity};
+pub use bus_factor::{AuthorShare, DirectoryKnowledge};
+pub use changelog::{
+    Changelog, ChangelogEntry, ChangelogError, ChangelogFormat, ChangelogOptions, ChangelogScope,
+    ChangelogSection,
+};
 pub use commit::Commit;
 use commit_author::CommitAuthor;
 pub use commit_commit::CommitCommit;
@@ -8,14 +16,32 @@ pub use commit_commit_tree::CommitCommitTree;
 pub use commit_parents_inner::CommitParentsInner;
 pub use commit_stats::CommitStats;
 pub use commit_status::CommitStatus;
+pub use compliance::{
+    CommitViolations, Compliance, ComplianceRule, ComplianceRules, ComplianceViolation,
+};
+pub use coupling::FileCoupling;
 pub use diff_entry::DiffEntry;
 // mod access_token;
 pub use git_user::GitUser;
+pub use hotspot::Hotspot;
+pub use language::LanguageBreakdown;
+pub use outlier::{OutlierCommit, OutlierMetric, OutlierReason, Outliers};
+pub use ownership::{FileOwnership, Ownership};
+pub use range::{RangeError, RangeSpec};
+pub use release::{BumpCommit, ReleaseError, ReleaseOptions, VersionBump, VersionRecommendation};
+pub use rework::{AuthorRework, FileRework, Rework};
 pub use simple_user::SimpleUser;
+pub use test_ratio::{AuthorTestRatio, CommitTestRatio, TestRatio};
 pub use verification::Verification;
+pub use work_pattern::{
+    AuthorWorkPattern, CommitHistogram, TeamWorkPattern, WorkPatterns, WorkingHours,
+};
 
 // mod arc_client;
+mod activity;
 mod base;
+mod bus_factor;
+mod changelog;
 mod commit;
 mod commit_author;
 mod commit_commit;
@@ -23,28 +49,41 @@ mod commit_commit_tree;
 mod commit_parents_inner;
 mod commit_stats;
 mod commit_status;
+mod compliance;
+mod coupling;
 mod diff_entry;
 mod git_user;
 mod github;
+mod hotspot;
+mod language;
+mod outlier;
+mod ownership;
+mod range;
+mod release;
+mod rework;
 mod simple_user;
+mod test_ratio;
 mod verification;
+mod work_pattern;
 // pub(crate) use arc_client::*;
 
 /// Represents the code churn (additions and deletions) for a specific commit.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeChurn {
     commit: String,
     additions: usize,
     deletions: usize,
+    files: Vec<FileChurn>,
 }
 
 impl CodeChurn {
     /// Creates a new `CodeChurn` instance.
-    pub fn new(commit: String, additions: usize, deletions: usize) -> Self {
+    pub fn new(commit: String, additions: usize, deletions: usize, files: Vec<FileChurn>) -> Self {
         CodeChurn {
             commit,
             additions,
             deletions,
+            files,
         }
     }
 
@@ -67,6 +106,22 @@ impl CodeChurn {
     pub fn total_changes(&self) -> usize {
         self.additions + self.deletions
     }
+
+    /// Returns the per-file churn of the commit.
+    pub fn files(&self) -> &[FileChurn] {
+        &self.files
+    }
+
+    /// Keeps only the files matching the predicate, recounting the commit's additions and
+    /// deletions from the files that remain.
+    pub fn retain_files(&mut self, f: impl FnMut(&FileChurn) -> bool) {
+        let count = self.files.len();
+        self.files.retain(f);
+        if self.files.len() != count {
+            self.additions = self.files.iter().map(FileChurn::additions).sum();
+            self.deletions = self.files.iter().map(FileChurn::deletions).sum();
+        }
+    }
 }
 
 impl AsRef<CodeChurn> for CodeChurn {
@@ -75,11 +130,313 @@ impl AsRef<CodeChurn> for CodeChurn {
     }
 }
 
+/// The kind of change a commit made to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+impl From<git2::Delta> for FileStatus {
+    fn from(delta: git2::Delta) -> Self {
+        match delta {
+            git2::Delta::Added | git2::Delta::Copied => FileStatus::Added,
+            git2::Delta::Deleted => FileStatus::Deleted,
+            git2::Delta::Renamed => FileStatus::Renamed,
+            _ => FileStatus::Modified,
+        }
+    }
+}
+
+/// Represents the code churn of a single file within a specific commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChurn {
+    commit: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_path: Option<String>,
+    status: FileStatus,
+    additions: usize,
+    deletions: usize,
+    binary: bool,
+}
+
+impl FileChurn {
+    /// Creates a new `FileChurn` instance.
+    pub fn new(
+        commit: String, path: String, old_path: Option<String>, status: FileStatus,
+        additions: usize, deletions: usize, binary: bool,
+    ) -> Self {
+        FileChurn {
+            commit,
+            path,
+            old_path,
+            status,
+            additions,
+            deletions,
+            binary,
+        }
+    }
+
+    /// Returns the commit hash.
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+
+    /// Returns the path of the file after the commit.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the path of the file before the commit, if it was renamed.
+    pub fn old_path(&self) -> Option<&str> {
+        self.old_path.as_deref()
+    }
+
+    /// Returns the kind of change made to the file.
+    pub fn status(&self) -> FileStatus {
+        self.status
+    }
+
+    /// Returns the number of additions.
+    pub fn additions(&self) -> usize {
+        self.additions
+    }
+
+    /// Returns the number of deletions.
+    pub fn deletions(&self) -> usize {
+        self.deletions
+    }
+
+    /// Returns the total changes (additions + deletions).
+    pub fn total_changes(&self) -> usize {
+        self.additions + self.deletions
+    }
+
+    /// Returns whether the file is binary.
+    pub fn is_binary(&self) -> bool {
+        self.binary
+    }
+}
+
+/// An optional section that can be included in a `CommitRangeAnalysis`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisSection {
+    /// Per-file churn for every commit in the range.
+    Files,
+    /// Files ranked by change frequency and churn.
+    Hotspots,
+    /// A directory tree of knowledge concentration and bus factors.
+    BusFactor,
+    /// Blame-based ownership of the surviving lines at the end commit.
+    Ownership,
+    /// Lines added in the range that were rewritten or deleted soon after landing.
+    Rework,
+    /// Pairs of files that are frequently modified in the same commit.
+    Coupling,
+    /// Commits, line changes and active authors bucketed by calendar period.
+    Activity,
+    /// Commits by hour of day and day of week, in each author's local time.
+    WorkPatterns,
+    /// Churn, files and authors per programming language.
+    Languages,
+    /// How well commit messages follow the Conventional Commits rules.
+    Compliance,
+    /// Test churn against production churn, and the commits changing code without tests.
+    TestRatio,
+    /// Commits with unusually large diffs, mass deletions, many files or added binaries.
+    Outliers,
+}
+
+/// Which commits of a range are analyzed, and how merge commits are treated.
+///
+/// Merge commits are always diffed against their first parent, so in `All` mode the changes
+/// of a merged branch are counted both in its own commits and in the merge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryMode {
+    /// Every commit reachable in the range.
+    #[default]
+    All,
+    /// Only commits on the first-parent chain, i.e. what landed on the main branch.
+    FirstParent,
+    /// Every commit in the range except merge commits.
+    NoMerges,
+    /// Only merge commits on the first-parent chain, each counted as a single squashed change.
+    MergesOnly,
+}
+
+/// Options controlling how a range of commits is analyzed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitRangeOptions {
+    /// The optional sections to include in the analysis.
+    #[serde(default)]
+    pub sections: Vec<AnalysisSection>,
+    /// Which commits of the range are analyzed.
+    #[serde(default)]
+    pub history_mode: HistoryMode,
+    /// Globs selecting the files to analyze, such as `src/**`. Every file is analyzed when empty.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+    /// Globs selecting files to leave out of the analysis, such as `docs/**`.
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+    /// Whether to leave lockfiles and vendored or generated files out of the analysis.
+    #[serde(default = "CommitRangeOptions::default_exclude_generated")]
+    pub exclude_generated: bool,
+    /// How many directory levels deep the bus factor tree goes.
+    #[serde(default = "CommitRangeOptions::default_bus_factor_depth")]
+    pub bus_factor_depth: usize,
+    /// How many days after landing a rewritten or deleted line still counts as rework.
+    #[serde(default = "CommitRangeOptions::default_rework_window_days")]
+    pub rework_window_days: u32,
+    /// How many commits must change a pair of files for it to count as coupled.
+    #[serde(default = "CommitRangeOptions::default_coupling_min_co_changes")]
+    pub coupling_min_co_changes: usize,
+    /// Maps author names or emails to the canonical email they belong to, on top of `.mailmap`.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+    /// The metric used to rank the top contributors.
+    #[serde(default)]
+    pub rank_contributors_by: ContributorRanking,
+    /// How commits with `Co-authored-by:` trailers are credited to their authors.
+    #[serde(default)]
+    pub co_author_credit: CoAuthorCredit,
+    /// Whether to leave commits authored by bots out of the analysis.
+    #[serde(default, alias = "include_only_humans")]
+    pub exclude_bots: bool,
+    /// Names or emails of additional accounts to treat as bots.
+    #[serde(default)]
+    pub bot_accounts: Vec<String>,
+    /// The calendar period that the activity series is bucketed by.
+    #[serde(default)]
+    pub granularity: Granularity,
+    /// The IANA timezone used to assign commits to calendar periods, such as `Europe/Amsterdam`.
+    #[serde(default = "CommitRangeOptions::default_timezone")]
+    pub timezone: String,
+    /// The hours outside of which commits are flagged as after-hours work.
+    #[serde(default)]
+    pub working_hours: WorkingHours,
+    /// Team names mapped to the canonical emails of their members.
+    #[serde(default)]
+    pub teams: HashMap<String, Vec<String>>,
+    /// The rules that commit messages are checked against.
+    #[serde(default)]
+    pub compliance_rules: ComplianceRules,
+    /// Globs marking the test files of a language, such as `Go` to `**/*_test.go`, replacing
+    /// the built-in conventions of that language.
+    #[serde(default)]
+    pub test_patterns: HashMap<String, Vec<String>>,
+    /// The modified z-score, against the median and median absolute deviation of the range,
+    /// above which a commit is flagged as an outlier.
+    #[serde(default = "CommitRangeOptions::default_outlier_threshold")]
+    pub outlier_threshold: f64,
+}
+
+impl Default for CommitRangeOptions {
+    fn default() -> Self {
+        Self {
+            sections: Vec::new(),
+            history_mode: HistoryMode::default(),
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            exclude_generated: Self::default_exclude_generated(),
+            bus_factor_depth: Self::default_bus_factor_depth(),
+            rework_window_days: Self::default_rework_window_days(),
+            coupling_min_co_changes: Self::default_coupling_min_co_changes(),
+            aliases: HashMap::new(),
+            rank_contributors_by: ContributorRanking::default(),
+            co_author_credit: CoAuthorCredit::default(),
+            exclude_bots: false,
+            bot_accounts: Vec::new(),
+            granularity: Granularity::default(),
+            timezone: Self::default_timezone(),
+            working_hours: WorkingHours::default(),
+            teams: HashMap::new(),
+            compliance_rules: ComplianceRules::default(),
+            test_patterns: HashMap::new(),
+            outlier_threshold: Self::default_outlier_threshold(),
+        }
+    }
+}
+
+impl CommitRangeOptions {
+    fn default_bus_factor_depth() -> usize {
+        2
+    }
+
+    fn default_rework_window_days() -> u32 {
+        21
+    }
+
+    fn default_coupling_min_co_changes() -> usize {
+        3
+    }
+
+    fn default_exclude_generated() -> bool {
+        true
+    }
+
+    fn default_timezone() -> String {
+        "UTC".to_string()
+    }
+
+    /// The cut-off recommended by Iglewicz and Hoaglin for modified z-scores.
+    fn default_outlier_threshold() -> f64 {
+        3.5
+    }
+
+    /// Returns whether the given section was requested.
+    pub fn includes(&self, section: AnalysisSection) -> bool {
+        self.sections.contains(&section)
+    }
+}
+
+/// The reasons the options of a commit range analysis are invalid, each naming the offending
+/// value.
+#[derive(Debug, Error)]
+pub enum OptionsError {
+    #[error("Unknown timezone: {0}")]
+    UnknownTimezone(String),
+    #[error("Invalid path glob: {0}")]
+    InvalidPathGlob(globset::Error),
+    #[error("Invalid test glob: {0}")]
+    InvalidTestGlob(globset::Error),
+}
+
 /// Represents an analysis of a range of commits in a repository.
 #[derive(Serialize, Deserialize)]
 pub struct CommitRangeAnalysis {
     pub repository: Repository,
     pub commit_range: CommitRangeDetails,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<FileChurn>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotspots: Option<Vec<Hotspot>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_factor: Option<DirectoryKnowledge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<Ownership>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rework: Option<Rework>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupling: Option<Vec<FileCoupling>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<ActivitySeries>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_patterns: Option<WorkPatterns>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<Vec<LanguageBreakdown>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<Compliance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_ratio: Option<TestRatio>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outliers: Option<Outliers>,
 }
 
 /// Contains details about a range of commits including total commits, additions, deletions, and top contributors.
@@ -93,22 +450,79 @@ pub struct CommitRangeDetails {
     pub top_contributors: Vec<Contributor>,
 }
 
-/// Represents a contributor with a username and the number of commits.
+/// Represents a contributor's activity within a range of commits.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Contributor {
     pub username: String,
+    pub email: String,
     pub commits: i32,
+    pub additions: i32,
+    pub deletions: i32,
+    /// The number of distinct files the contributor changed.
+    pub files_touched: i32,
+    /// Whether the contributor is an automation account rather than a person.
+    pub is_bot: bool,
 }
 
 impl Contributor {
-    /// Creates a new `Contributor` instance.
-    pub fn new(username: String, commits: i32) -> Self {
-        Self { username, commits }
+    /// Creates a new `Contributor` instance with no recorded activity.
+    pub fn new(username: String, email: String) -> Self {
+        Self {
+            username,
+            email,
+            commits: 0,
+            additions: 0,
+            deletions: 0,
+            files_touched: 0,
+            is_bot: false,
+        }
     }
+}
+
+/// How the work of a commit is credited when it has `Co-authored-by:` trailers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoAuthorCredit {
+    /// The author and every co-author each get full credit for the commit.
+    #[default]
+    Full,
+    /// The commit's counts are split equally between the author and co-authors.
+    Split,
+}
 
-    /// Adds a number of commits to the contributor.
-    pub fn add_commits(&mut self, commits: i32) {
-        self.commits += commits;
+/// The metric used to rank contributors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContributorRanking {
+    #[default]
+    Commits,
+    Additions,
+    Deletions,
+    /// Additions plus deletions.
+    Changes,
+    FilesTouched,
+}
+
+impl ContributorRanking {
+    /// Returns the value of the ranking metric for a contributor.
+    pub fn key(&self, contributor: &Contributor) -> i32 {
+        match self {
+            ContributorRanking::Commits => contributor.commits,
+            ContributorRanking::Additions => contributor.additions,
+            ContributorRanking::Deletions => contributor.deletions,
+            ContributorRanking::Changes => contributor.additions + contributor.deletions,
+            ContributorRanking::FilesTouched => contributor.files_touched,
+        }
+    }
+
+    /// Sorts contributors by this metric, highest first.
+    pub fn sort(&self, contributors: &mut [Contributor]) {
+        contributors.sort_by(|a, b| {
+            self.key(b)
+                .cmp(&self.key(a))
+                .then(a.username.cmp(&b.username))
+                .then(a.email.cmp(&b.email))
+        });
     }
 }
 
@@ -260,4 +674,38 @@ impl Repository {
             },
         }
     }
+
+    /// Returns the web page of a commit, or `None` for custom repositories.
+    pub fn commit_url(&self, id: &str) -> Option<String> {
+        let path = match self {
+            Repository::GitHub(_) | Repository::AzureRepos(_) => "commit",
+            Repository::GitLab(_) => "-/commit",
+            Repository::Bitbucket(_) => "commits",
+            Repository::Custom(_) => return None,
+        };
+        Some(format!("{}/{}/{}", self.url(Protocol::Http).ok()?, path, id))
+    }
+
+    /// Returns the web page of a pull request, or `None` for custom repositories.
+    pub fn pull_request_url(&self, number: u64) -> Option<String> {
+        let path = match self {
+            Repository::GitHub(_) => "pull",
+            Repository::GitLab(_) => "-/merge_requests",
+            Repository::Bitbucket(_) => "pull-requests",
+            Repository::AzureRepos(_) => "pullrequest",
+            Repository::Custom(_) => return None,
+        };
+        Some(format!("{}/{}/{}", self.url(Protocol::Http).ok()?, path, number))
+    }
+
+    /// Returns the profile page of the author of a commit, which is only known for GitHub
+    /// `noreply` emails such as `123+octocat@users.noreply.github.com`.
+    pub fn author_url(&self, email: &str) -> Option<String> {
+        let Repository::GitHub(_) = self else {
+            return None;
+        };
+        let login = email.strip_suffix("@users.noreply.github.com")?;
+        let login = login.split_once('+').map_or(login, |(_, login)| login);
+        Some(format!("https://github.com/{}", login))
+    }
 }
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::annotations::prelude::{Annotation, AnnotationLevel};
+
+/// A way in which a commit can stand out from the usual commits of its repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlierMetric {
+    /// The commit added and deleted far more lines than usual.
+    Churn,
+    /// The commit deleted far more lines than usual, and more than it added.
+    Deletions,
+    /// The commit touched far more files than usual.
+    Files,
+    /// The commit added binary files.
+    BinaryFiles,
+}
+
+/// Represents a reason a commit was flagged as an outlier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlierReason {
+    pub metric: OutlierMetric,
+    /// The value of the metric for the commit.
+    pub value: usize,
+    /// The median of the metric over the baseline, or `None` for binary files, which are
+    /// flagged whenever they are added.
+    pub median: Option<f64>,
+    /// The modified z-score of the commit, the number of robust standard deviations it lies
+    /// above the median, or `None` for binary files.
+    pub score: Option<f64>,
+    pub message: String,
+}
+
+/// Represents a commit that stands out from the usual commits of its repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlierCommit {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    /// The first line of the commit message.
+    pub subject: String,
+    pub additions: usize,
+    pub deletions: usize,
+    pub files: usize,
+    pub reasons: Vec<OutlierReason>,
+}
+
+/// Represents the statistically unusual commits of a range, which skew its averages.
+///
+/// Commits are measured against the median and median absolute deviation of a baseline, the
+/// most recent commits of the history up to the end of the range, so that what counts as
+/// unusual follows the habits of the repository even for a short range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Outliers {
+    /// The number of commits measured, which changed at least one selected file.
+    pub measured: usize,
+    /// The number of commits of the baseline, the most recent ones up to the end of the range
+    /// that changed at least one selected file. Only binary files are flagged when it is too
+    /// small to tell what is usual.
+    pub baseline: usize,
+    /// The modified z-score above which a commit is flagged.
+    pub threshold: f64,
+    /// The flagged commits, newest first.
+    pub commits: Vec<OutlierCommit>,
+}
+
+impl Outliers {
+    /// Converts every reason into an annotation that CI annotators can issue on a pull request.
+    ///
+    /// Annotations are not tied to a line, and name the commit as their file.
+    pub fn annotations(&self, level: AnnotationLevel) -> Vec<Annotation> {
+        let level = &level;
+        self.commits
+            .iter()
+            .flat_map(|commit| {
+                let short_id = &commit.commit[..commit.commit.len().min(7)];
+                commit.reasons.iter().map(move |reason| {
+                    Annotation::new_whole_file(
+                        commit.commit.clone(),
+                        format!("{} \"{}\": {}", short_id, commit.subject, reason.message),
+                        level.clone(),
+                    )
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::AuthorShare;
+
+/// Represents who owns the surviving lines of a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileOwnership {
+    pub path: String,
+    pub lines: usize,
+    pub authors: Vec<AuthorShare>,
+}
+
+/// Represents who owns the surviving lines of a repository at a given commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ownership {
+    pub commit: String,
+    pub lines: usize,
+    pub authors: Vec<AuthorShare>,
+    pub files: Vec<FileOwnership>,
+}
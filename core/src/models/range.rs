@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Specifies the commits of a range.
+///
+/// The ends of the range are given by a pair of revisions, a revspec or the two most recent
+/// tags, and default to the whole history of `HEAD`. The commits between them can then be
+/// narrowed down by date and count.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RangeSpec {
+    /// The revision the range starts after, exclusive. The range covers the whole history of
+    /// its end when `None`.
+    #[serde(default)]
+    pub start_commit: Option<String>,
+    /// The revision the range ends at, inclusive. Defaults to `HEAD`.
+    #[serde(default)]
+    pub end_commit: Option<String>,
+    /// A revspec such as `v1.2.0..main`, or a single revision such as `HEAD~50` for its whole
+    /// history.
+    #[serde(default)]
+    pub revspec: Option<String>,
+    /// Whether the range goes from the second most recent tag to the most recent one.
+    #[serde(default)]
+    pub between_latest_tags: bool,
+    /// Only commits made at or after this time, as an RFC 3339 timestamp or a `YYYY-MM-DD` date
+    /// in UTC.
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Only commits made at or before this time, as an RFC 3339 timestamp or a `YYYY-MM-DD` date
+    /// in UTC, which includes the whole day.
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Only the given number of most recent commits of the range.
+    #[serde(default)]
+    pub last: Option<usize>,
+    /// The most commits the range may hold. Larger ranges are rejected rather than truncated.
+    #[serde(default)]
+    pub max_commits: Option<usize>,
+}
+
+/// The reasons a range spec cannot be resolved, each naming the offending token.
+#[derive(Debug, Error)]
+pub enum RangeError {
+    #[error("Invalid revision: {0}")]
+    InvalidRevision(String),
+    #[error("Unsupported revspec: {0}, symmetric differences are not supported")]
+    SymmetricDifference(String),
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+    #[error("Conflicting range fields: {0} cannot be combined with {1}")]
+    Conflict(&'static str, &'static str),
+    #[error("Not enough tags: between_latest_tags needs two tagged commits, found {0}")]
+    NotEnoughTags(usize),
+    #[error("Too many commits: the range holds more than max_commits ({0})")]
+    TooManyCommits(usize),
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::HistoryMode;
+
+/// How much a version is bumped, from least to most significant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Options controlling how the next version of a repository is determined.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleaseOptions {
+    /// The pre-release identifier to release under, such as `rc` for `2.0.0-rc.1`, or `None`
+    /// for a stable release.
+    #[serde(default)]
+    pub pre_release: Option<String>,
+    /// The prefix of release tags, such as `core-` for `core-v1.2.3`. Tags may add a `v`
+    /// between the prefix and the version.
+    #[serde(default)]
+    pub tag_prefix: String,
+    /// Which commits since the latest release are considered.
+    #[serde(default)]
+    pub history_mode: HistoryMode,
+}
+
+/// Represents a conventional commit that calls for a version bump.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BumpCommit {
+    pub commit: String,
+    pub commit_type: String,
+    pub scope: Option<String>,
+    pub description: String,
+    /// Whether the commit is marked as breaking or has a `BREAKING CHANGE` footer.
+    pub breaking: bool,
+}
+
+/// Represents the recommended next version of a repository, following Semantic Versioning.
+///
+/// Breaking changes bump the major version, features the minor version and fixes and
+/// performance improvements the patch version. While the major version is 0, breaking changes
+/// only bump the minor version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionRecommendation {
+    /// The latest stable release tag reachable from the target, if any.
+    pub current_tag: Option<String>,
+    /// The version of the latest stable release, if any.
+    pub current_version: Option<String>,
+    /// The recommended next version, which is the current version when nothing calls for a
+    /// release.
+    pub next_version: String,
+    /// The bump from the current to the next version, or `None` when nothing calls for a
+    /// release.
+    pub bump: Option<VersionBump>,
+    /// The commits since the current release that call for the bump, newest first.
+    pub commits: Vec<BumpCommit>,
+}
+
+/// The reasons the next version of a repository cannot be determined.
+#[derive(Debug, Error)]
+pub enum ReleaseError {
+    #[error("Invalid pre-release identifier: {0}")]
+    InvalidPreRelease(String),
+    #[error("Invalid revision: {0}")]
+    InvalidRevision(String),
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents how much of an author's new code was rewritten or deleted soon after it landed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorRework {
+    pub author: String,
+    pub email: String,
+    pub added: usize,
+    pub reworked: usize,
+    /// The fraction of added lines (between 0 and 1) that were reworked.
+    pub rework_ratio: f64,
+}
+
+/// Represents how much of the new code in a file was rewritten or deleted soon after it landed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRework {
+    pub path: String,
+    pub added: usize,
+    pub reworked: usize,
+    /// The fraction of added lines (between 0 and 1) that were reworked.
+    pub rework_ratio: f64,
+}
+
+/// Represents code survival within a range of commits.
+///
+/// A line counts as reworked when it was added inside the range and then rewritten or deleted
+/// by a later commit in the range within `window_days` of being added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rework {
+    pub window_days: u32,
+    pub added: usize,
+    pub reworked: usize,
+    /// The fraction of added lines (between 0 and 1) that were reworked.
+    pub rework_ratio: f64,
+    pub authors: Vec<AuthorRework>,
+    pub files: Vec<FileRework>,
+}
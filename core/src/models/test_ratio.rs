@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the test and production churn of a commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitTestRatio {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    pub test_additions: usize,
+    pub test_deletions: usize,
+    pub production_additions: usize,
+    pub production_deletions: usize,
+    /// Whether the commit changed production code without changing any test code.
+    pub untested: bool,
+}
+
+/// Represents the test and production churn of an author.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorTestRatio {
+    pub author: String,
+    pub email: String,
+    /// The commits of the author that changed code.
+    pub commits: usize,
+    pub untested_commits: usize,
+    pub test_additions: usize,
+    pub test_deletions: usize,
+    pub production_additions: usize,
+    pub production_deletions: usize,
+    /// The test churn divided by the production churn, or `None` when no production code
+    /// changed.
+    pub test_ratio: Option<f64>,
+}
+
+/// Represents how much test code changed alongside production code within a range of commits.
+///
+/// Only files of languages with test conventions count as code. Commits that change no code
+/// are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRatio {
+    pub test_additions: usize,
+    pub test_deletions: usize,
+    pub production_additions: usize,
+    pub production_deletions: usize,
+    /// The test churn divided by the production churn, or `None` when no production code
+    /// changed.
+    pub test_ratio: Option<f64>,
+    pub untested_commits: usize,
+    pub authors: Vec<AuthorTestRatio>,
+    /// The commits that changed code, newest first.
+    pub commits: Vec<CommitTestRatio>,
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// The hours considered normal working time, in each author's local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingHours {
+    /// The first working hour of the day, from 0 to 23.
+    pub start_hour: u32,
+    /// The hour at which the working day ends, exclusive.
+    pub end_hour: u32,
+    /// Whether Saturday and Sunday are working days.
+    #[serde(default)]
+    pub weekends: bool,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 9,
+            end_hour: 18,
+            weekends: false,
+        }
+    }
+}
+
+/// Represents when commits were made, in their authors' local time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitHistogram {
+    pub commits: usize,
+    /// Commits per hour of the day, from midnight.
+    pub by_hour: Vec<usize>,
+    /// Commits per day of the week, from Monday.
+    pub by_weekday: Vec<usize>,
+    /// Commits made outside of working hours.
+    pub after_hours: usize,
+    /// The fraction of commits (between 0 and 1) made outside of working hours.
+    pub after_hours_ratio: f64,
+}
+
+/// Represents when an author commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorWorkPattern {
+    pub author: String,
+    pub email: String,
+    pub histogram: CommitHistogram,
+}
+
+/// Represents when the members of a team commit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamWorkPattern {
+    pub team: String,
+    pub histogram: CommitHistogram,
+}
+
+/// Represents the distribution of commits over the hours of the day and days of the week.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkPatterns {
+    pub working_hours: WorkingHours,
+    /// The distribution over every author in the range.
+    pub overall: CommitHistogram,
+    pub authors: Vec<AuthorWorkPattern>,
+    pub teams: Vec<TeamWorkPattern>,
+}
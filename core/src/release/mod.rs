@@ -0,0 +1,267 @@
+use std::error::Error;
+
+use git2::{Oid, Repository, Sort};
+use semver::{Prerelease, Version};
+
+use crate::commit::conventional::Commit;
+use crate::models::{BumpCommit, ReleaseError, ReleaseOptions, VersionBump, VersionRecommendation};
+use crate::repository::CommitFilter;
+
+/// A tag naming a version.
+struct ReleaseTag {
+    name: String,
+    version: Version,
+    commit: Oid,
+}
+
+/// Recommends the next version of a repository at a target revision.
+///
+/// The bump is decided by the conventional commits since the latest stable release tag that
+/// is reachable from the target. Pre-releases are numbered after the earlier pre-releases of
+/// the same version, as in `2.0.0-rc.1`, `2.0.0-rc.2`.
+pub(crate) fn recommend_version(
+    repo: &Repository, target: &str, options: &ReleaseOptions,
+) -> Result<VersionRecommendation, Box<dyn Error + Send + Sync>> {
+    if let Some(identifier) = &options.pre_release {
+        if identifier.is_empty() || Prerelease::new(identifier).is_err() {
+            return Err(ReleaseError::InvalidPreRelease(identifier.clone()).into());
+        }
+    }
+
+    let target = repo
+        .revparse_single(target)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| ReleaseError::InvalidRevision(target.to_string()))?
+        .id();
+    let tags = release_tags(repo, target, &options.tag_prefix)?;
+    let current = tags
+        .iter()
+        .filter(|tag| tag.version.pre.is_empty())
+        .max_by(|a, b| a.version.cmp(&b.version));
+    let base = current.map_or_else(|| Version::new(0, 0, 0), |tag| tag.version.clone());
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(target)?;
+    if let Some(current) = current {
+        revwalk.hide(current.commit)?;
+    }
+    revwalk.set_sorting(Sort::TOPOLOGICAL)?;
+    let filter = CommitFilter::new(options.history_mode);
+
+    let mut commits = Vec::new();
+    for id in filter.commits(repo, revwalk)? {
+        let commit = repo.find_commit(id?)?;
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        let merge = commit.parent_count() > 1 && filter.follows_first_parent();
+        let Some(conventional) = Commit::from_commit_message(&message, merge) else {
+            continue;
+        };
+        if let Some(bump) = bump_of(&conventional, base.major == 0) {
+            let commit = BumpCommit {
+                commit: commit.id().to_string(),
+                commit_type: conventional.commit_type().to_lowercase(),
+                scope: conventional.scope().map(str::to_string),
+                description: conventional.description().to_string(),
+                breaking: conventional.is_breaking(),
+            };
+            commits.push((bump, commit));
+        }
+    }
+
+    let bump = commits.iter().map(|(bump, _)| *bump).max();
+    let mut next = bump.map_or_else(|| base.clone(), |bump| bumped(&base, bump));
+    if let (Some(_), Some(identifier)) = (bump, &options.pre_release) {
+        let number = tags
+            .iter()
+            .filter(|tag| {
+                (tag.version.major, tag.version.minor, tag.version.patch)
+                    == (next.major, next.minor, next.patch)
+            })
+            .filter_map(|tag| {
+                let pre = tag.version.pre.as_str().strip_prefix(identifier.as_str())?;
+                pre.strip_prefix('.')?.parse::<u64>().ok()
+            })
+            .max()
+            .map_or(1, |number| number + 1);
+        next.pre = Prerelease::new(&format!("{}.{}", identifier, number))?;
+    }
+
+    Ok(VersionRecommendation {
+        current_tag: current.map(|tag| tag.name.clone()),
+        current_version: current.map(|tag| tag.version.to_string()),
+        next_version: next.to_string(),
+        bump,
+        commits: commits
+            .into_iter()
+            .filter(|(commit_bump, _)| Some(*commit_bump) == bump)
+            .map(|(_, commit)| commit)
+            .collect(),
+    })
+}
+
+/// Lists the tags naming a version that are reachable from the target commit.
+fn release_tags(
+    repo: &Repository, target: Oid, prefix: &str,
+) -> Result<Vec<ReleaseTag>, git2::Error> {
+    let mut tags = Vec::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        let Some(version) = parse_tag(name, prefix) else {
+            continue;
+        };
+        // Tags of trees or blobs do not name a release.
+        let Ok(commit) = repo
+            .revparse_single(&format!("refs/tags/{}", name))
+            .and_then(|object| object.peel_to_commit())
+        else {
+            continue;
+        };
+        if commit.id() == target || repo.graph_descendant_of(target, commit.id())? {
+            tags.push(ReleaseTag {
+                name: name.to_string(),
+                version,
+                commit: commit.id(),
+            });
+        }
+    }
+    Ok(tags)
+}
+
+/// Parses the version named by a tag such as `v1.2.3` or `1.2.3`, after the given prefix.
+fn parse_tag(name: &str, prefix: &str) -> Option<Version> {
+    let version = name.strip_prefix(prefix)?;
+    let version = version.strip_prefix('v').unwrap_or(version);
+    Version::parse(version).ok()
+}
+
+/// Returns the bump a commit calls for, if any.
+fn bump_of(commit: &Commit, initial_development: bool) -> Option<VersionBump> {
+    if commit.is_breaking() {
+        return Some(if initial_development {
+            VersionBump::Minor
+        } else {
+            VersionBump::Major
+        });
+    }
+    match commit.commit_type().to_lowercase().as_str() {
+        "feat" => Some(VersionBump::Minor),
+        "fix" | "perf" => Some(VersionBump::Patch),
+        _ => None,
+    }
+}
+
+fn bumped(version: &Version, bump: VersionBump) -> Version {
+    match bump {
+        VersionBump::Major => Version::new(version.major + 1, 0, 0),
+        VersionBump::Minor => Version::new(version.major, version.minor + 1, 0),
+        VersionBump::Patch => Version::new(version.major, version.minor, version.patch + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::HistoryMode;
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    fn tag(test_repo: &TestRepo, name: &str, id: Oid) {
+        let object = test_repo.repo.find_object(id, None).unwrap();
+        test_repo
+            .repo
+            .tag_lightweight(name, &object, false)
+            .unwrap();
+    }
+
+    fn options(pre_release: Option<&str>) -> ReleaseOptions {
+        ReleaseOptions {
+            pre_release: pre_release.map(str::to_string),
+            ..ReleaseOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_recommend_version_from_latest_tag() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let initial = test_repo.commit("feat: initial release");
+        tag(&test_repo, "v0.9.0", initial);
+        test_repo.write("src/lib.rs", "pub fn a() {}\n");
+        let release = test_repo.commit("fix: handle empty input");
+        tag(&test_repo, "v1.0.0", release);
+        tag(&test_repo, "not-a-version", release);
+
+        let recommendation = recommend_version(&test_repo.repo, "HEAD", &options(None)).unwrap();
+        assert_eq!(recommendation.current_tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(recommendation.next_version, "1.0.0");
+        assert_eq!(recommendation.bump, None);
+
+        test_repo.write("src/lib.rs", "pub fn b() {}\n");
+        test_repo.commit("fix: handle unicode input");
+        test_repo.write("README.md", "hello world\n");
+        test_repo.commit("docs: describe usage");
+        test_repo.write("src/lib.rs", "pub fn c() {}\n");
+        let feature = test_repo.commit("feat(api): add c");
+
+        let recommendation = recommend_version(&test_repo.repo, "HEAD", &options(None)).unwrap();
+        assert_eq!(recommendation.current_version.as_deref(), Some("1.0.0"));
+        assert_eq!(recommendation.next_version, "1.1.0");
+        assert_eq!(recommendation.bump, Some(VersionBump::Minor));
+        assert_eq!(recommendation.commits.len(), 1);
+        assert_eq!(recommendation.commits[0].commit, feature.to_string());
+        assert_eq!(recommendation.commits[0].scope.as_deref(), Some("api"));
+
+        test_repo.write("src/lib.rs", "pub fn d() {}\n");
+        test_repo.commit("refactor: rename c to d\n\nBREAKING CHANGE: c is gone");
+        let recommendation = recommend_version(&test_repo.repo, "HEAD", &options(None)).unwrap();
+        assert_eq!(recommendation.next_version, "2.0.0");
+        assert_eq!(recommendation.commits.len(), 1);
+        assert!(recommendation.commits[0].breaking);
+
+        // Only tags reachable from the target count.
+        let recommendation =
+            recommend_version(&test_repo.repo, &initial.to_string(), &options(None)).unwrap();
+        assert_eq!(recommendation.current_tag.as_deref(), Some("v0.9.0"));
+        assert_eq!(recommendation.next_version, "0.9.0");
+    }
+
+    #[test]
+    fn test_recommend_version_initial_development_and_pre_release() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let release = test_repo.commit("feat: initial release");
+        tag(&test_repo, "core-v0.3.1", release);
+        test_repo.write("src/lib.rs", "pub fn a() {}\n");
+        test_repo.commit("feat!: replace the API");
+
+        let options = ReleaseOptions {
+            pre_release: Some("rc".to_string()),
+            tag_prefix: "core-".to_string(),
+            history_mode: HistoryMode::All,
+        };
+        let recommendation = recommend_version(&test_repo.repo, "HEAD", &options).unwrap();
+        assert_eq!(recommendation.current_tag.as_deref(), Some("core-v0.3.1"));
+        assert_eq!(recommendation.bump, Some(VersionBump::Minor));
+        assert_eq!(recommendation.next_version, "0.4.0-rc.1");
+
+        let head = test_repo.repo.head().unwrap().target().unwrap();
+        tag(&test_repo, "core-v0.4.0-rc.1", head);
+        test_repo.write("src/lib.rs", "pub fn b() {}\n");
+        test_repo.commit("fix: handle empty input");
+        let recommendation = recommend_version(&test_repo.repo, "HEAD", &options).unwrap();
+        assert_eq!(recommendation.next_version, "0.4.0-rc.2");
+
+        let error = recommend_version(&test_repo.repo, "HEAD", &self::options(Some("rc 1")));
+        assert!(error.unwrap_err().is::<ReleaseError>());
+    }
+
+    #[test]
+    fn test_recommend_version_rejects_unknown_target() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        test_repo.commit("feat: initial release");
+
+        let error = recommend_version(&test_repo.repo, "mian", &options(None)).unwrap_err();
+        assert!(error.is::<ReleaseError>());
+        assert_eq!(error.to_string(), "Invalid revision: mian");
+    }
+}
@@ -0,0 +1,124 @@
+use git2::{Commit, Error, ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use globset::{GlobBuilder, GlobMatcher};
+
+/// The attributes assigned to paths by the `.gitattributes` files of a commit.
+///
+/// Patterns follow the `.gitattributes` rules: a pattern without a slash matches a file name
+/// at any depth below its file's directory, and a pattern with a slash is relative to it.
+/// Deeper files take precedence over shallower ones, and later lines over earlier ones. Macro
+/// attributes are not expanded.
+#[derive(Debug, Default)]
+pub struct GitAttributes {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    pattern: GlobMatcher,
+    /// Attribute names with their value, or `None` when the line resets them to unspecified.
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl GitAttributes {
+    /// Reads every `.gitattributes` file in the tree of a commit.
+    pub fn load(repo: &Repository, commit: &Commit) -> Result<Self, Error> {
+        let mut files = Vec::new();
+        commit.tree()?.walk(TreeWalkMode::PreOrder, |root, entry| {
+            if entry.kind() == Some(ObjectType::Blob) && entry.name() == Some(".gitattributes") {
+                files.push((root.to_string(), entry.id()));
+            }
+            TreeWalkResult::Ok
+        })?;
+        files.sort_by_key(|(dir, _)| dir.matches('/').count());
+
+        let mut attributes = Self::default();
+        for (dir, id) in files {
+            let blob = repo.find_blob(id)?;
+            attributes.parse(&dir, &String::from_utf8_lossy(blob.content()));
+        }
+        Ok(attributes)
+    }
+
+    /// Adds the rules of a `.gitattributes` file located in `dir`, which is empty for the root
+    /// or ends with a slash.
+    fn parse(&mut self, dir: &str, contents: &str) {
+        for line in contents.lines() {
+            let mut tokens = line.split_whitespace();
+            let Some(pattern) = tokens.next() else {
+                continue;
+            };
+            if pattern.starts_with('#') || pattern.starts_with("[attr]") || pattern.ends_with('/') {
+                continue;
+            }
+
+            let glob = match pattern.strip_prefix('/') {
+                Some(pattern) => format!("{}{}", dir, pattern),
+                None if pattern.contains('/') => format!("{}{}", dir, pattern),
+                None => format!("{}**/{}", dir, pattern),
+            };
+            // A malformed line is ignored rather than failing the whole analysis.
+            let Ok(glob) = GlobBuilder::new(&glob).literal_separator(true).build() else {
+                continue;
+            };
+
+            let attributes = tokens
+                .map(|token| {
+                    if let Some(name) = token.strip_prefix('-') {
+                        (name.to_string(), Some("false".to_string()))
+                    } else if let Some(name) = token.strip_prefix('!') {
+                        (name.to_string(), None)
+                    } else if let Some((name, value)) = token.split_once('=') {
+                        (name.to_string(), Some(value.to_string()))
+                    } else {
+                        (token.to_string(), Some("true".to_string()))
+                    }
+                })
+                .collect();
+            self.rules.push(Rule {
+                pattern: glob.compile_matcher(),
+                attributes,
+            });
+        }
+    }
+
+    /// Returns the value of an attribute for a path, which is `"true"` when the attribute is set
+    /// and `"false"` when it is unset, or `None` when it is unspecified.
+    pub fn get(&self, path: &str, name: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .rev()
+            .filter(|rule| rule.pattern.is_match(path))
+            .find_map(|rule| {
+                rule.attributes
+                    .iter()
+                    .rev()
+                    .find(|(attribute, _)| attribute == name)
+            })
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Returns whether a boolean attribute is set for a path, or `None` when it is unspecified.
+    pub fn is_set(&self, path: &str, name: &str) -> Option<bool> {
+        self.get(path, name).map(|value| value != "false")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_precedence() {
+        let mut attributes = GitAttributes::default();
+        attributes
+            .parse("", "# comment\n*.pb.rs linguist-generated\n/docs/** linguist-documentation\n");
+        attributes.parse("gen/", "*.pb.rs -linguist-generated\nkeep.rs linguist-language=Rust\n");
+
+        assert_eq!(attributes.is_set("src/api.pb.rs", "linguist-generated"), Some(true));
+        assert_eq!(attributes.is_set("gen/api.pb.rs", "linguist-generated"), Some(false));
+        assert_eq!(attributes.is_set("src/docs/index.md", "linguist-documentation"), None);
+        assert_eq!(attributes.is_set("docs/guide/index.md", "linguist-documentation"), Some(true));
+        assert_eq!(attributes.get("gen/nested/keep.rs", "linguist-language"), Some("Rust"));
+        assert_eq!(attributes.get("keep.rs", "linguist-language"), None);
+    }
+}
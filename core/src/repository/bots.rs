@@ -0,0 +1,57 @@
+/// Patterns that identify well-known automation accounts by name or email.
+const BOT_PATTERNS: &[&str] = &["[bot]", "dependabot", "renovate", "github-actions"];
+
+/// Classifies commit authors as bots or humans.
+///
+/// An author is a bot when its name or email contains one of the well-known bot patterns, or
+/// when either exactly matches one of the user-supplied accounts, ignoring case.
+#[derive(Debug, Clone, Default)]
+pub struct BotDetector {
+    accounts: Vec<String>,
+}
+
+impl BotDetector {
+    /// Also treats the given names or emails as bots.
+    pub fn with_accounts(mut self, accounts: Vec<String>) -> Self {
+        self.accounts = accounts
+            .into_iter()
+            .map(|account| account.to_lowercase())
+            .collect();
+        self
+    }
+
+    /// Returns whether the author with the given name and email is a bot.
+    pub fn is_bot(&self, name: &str, email: &str) -> bool {
+        let (name, email) = (name.to_lowercase(), email.to_lowercase());
+        BOT_PATTERNS
+            .iter()
+            .any(|pattern| name.contains(pattern) || email.contains(pattern))
+            || self
+                .accounts
+                .iter()
+                .any(|account| *account == name || *account == email)
+    }
+
+    /// Returns whether the author of a commit signature is a bot.
+    pub fn is_bot_signature(&self, signature: &git2::Signature) -> bool {
+        self.is_bot(signature.name().unwrap_or_default(), signature.email().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bot() {
+        let bots = BotDetector::default().with_accounts(vec!["Release Robot".to_string()]);
+
+        assert!(bots.is_bot("dependabot[bot]", "49699333+dependabot[bot]@users.noreply.github.com"));
+        assert!(bots.is_bot("Renovate Bot", "bot@renovateapp.com"));
+        assert!(
+            bots.is_bot("github-actions", "41898282+github-actions[bot]@users.noreply.github.com")
+        );
+        assert!(bots.is_bot("release robot", "release@example.com"));
+        assert!(!bots.is_bot("Jane Doe", "jane@example.com"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use git2::{Commit, Oid, Repository};
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+
+use super::{diff_commit, CommitDiff};
+
+/// The version of the cached entries. Bump it whenever what is recorded for a commit changes,
+/// so that the entries written by earlier versions are dropped.
+const CACHE_VERSION: u64 = 3;
+
+/// The records of commits, as JSON, keyed by the raw bytes of their id.
+const COMMITS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("commits");
+
+/// Facts about the cache itself, such as its version.
+const METADATA: TableDefinition<&str, u64> = TableDefinition::new("metadata");
+
+/// The databases opened by this process by path, as a database can only be opened once.
+static DATABASES: OnceLock<Mutex<HashMap<PathBuf, Arc<Database>>>> = OnceLock::new();
+
+/// What is recorded about a commit: its author, its time and its diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedCommit {
+    /// The author's name and email as written in the commit, before `.mailmap` and aliases.
+    pub author_name: String,
+    pub author_email: String,
+    /// The commit time, in seconds since the epoch.
+    pub time: i64,
+    pub diff: CommitDiff,
+}
+
+impl CachedCommit {
+    /// Records a commit, diffing it against its first parent.
+    pub fn new(repo: &Repository, commit: &Commit) -> Result<Self, git2::Error> {
+        let author = commit.author();
+        Ok(Self {
+            author_name: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+            author_email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+            time: commit.time().seconds(),
+            diff: diff_commit(repo, commit)?,
+        })
+    }
+}
+
+/// A persistent cache of the records of commits, keyed by commit id.
+///
+/// Commits never change, so their diffs, authors and times are only read the first time any
+/// range covers them. The cache is an embedded on-disk store, kept next to the clone of the repository.
+#[derive(Clone)]
+pub struct CommitCache {
+    database: Arc<Database>,
+}
+
+impl CommitCache {
+    /// Opens the cache at the given path, creating it if needed.
+    ///
+    /// Entries written by another version of the cache are dropped.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let path = path.as_ref();
+        let mut databases = DATABASES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(database) = databases.get(path) {
+            return Ok(Self {
+                database: database.clone(),
+            });
+        }
+
+        let cache = Self::new(Database::create(path)?)?;
+        databases.insert(path.to_path_buf(), cache.database.clone());
+        Ok(cache)
+    }
+
+    /// Wraps an open database, clearing it when it holds entries of another version.
+    fn new(database: Database) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let transaction = database.begin_write()?;
+        {
+            let mut metadata = transaction.open_table(METADATA)?;
+            let version = metadata.get("version")?.map(|version| version.value());
+            if version != Some(CACHE_VERSION) {
+                transaction.delete_table(COMMITS)?;
+                metadata.insert("version", CACHE_VERSION)?;
+            }
+            transaction.open_table(COMMITS)?;
+        }
+        transaction.commit()?;
+        Ok(Self {
+            database: Arc::new(database),
+        })
+    }
+
+    /// Looks up the records of the given commits, in the same order.
+    ///
+    /// Entries that cannot be read are treated as missing.
+    pub fn get(
+        &self, ids: &[Oid],
+    ) -> Result<Vec<Option<CachedCommit>>, Box<dyn Error + Send + Sync>> {
+        let transaction = self.database.begin_read()?;
+        let commits = transaction.open_table(COMMITS)?;
+        ids.iter()
+            .map(|id| -> Result<_, Box<dyn Error + Send + Sync>> {
+                let entry = commits.get(id.as_bytes())?;
+                Ok(entry.and_then(|entry| serde_json::from_slice(entry.value()).ok()))
+            })
+            .collect()
+    }
+
+    /// Stores the records of the given commits.
+    pub fn insert(
+        &self, ids: &[Oid], records: &[CachedCommit],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let transaction = self.database.begin_write()?;
+        {
+            let mut commits = transaction.open_table(COMMITS)?;
+            for (id, record) in ids.iter().zip(records) {
+                let value = serde_json::to_vec(record)?;
+                commits.insert(id.as_bytes(), value.as_slice())?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_and_versioning() {
+        let test_repo = TestRepo::new();
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        let id = test_repo.commit("add main");
+        let commit = test_repo.repo.find_commit(id).unwrap();
+        let record = CachedCommit::new(&test_repo.repo, &commit).unwrap();
+        let missing = Oid::from_str("0123456789abcdef0123456789abcdef01234567").unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commits.redb");
+        let cache = CommitCache::open(&path).unwrap();
+        cache.insert(&[id], &[record]).unwrap();
+        // The same database is shared by every cache opened at the same path.
+        let cached = CommitCache::open(&path)
+            .unwrap()
+            .get(&[missing, id])
+            .unwrap();
+        assert!(cached[0].is_none());
+        let cached = cached[1].as_ref().unwrap();
+        assert_eq!(cached.author_email, commit.author().email().unwrap());
+        assert_eq!(cached.time, commit.time().seconds());
+        assert_eq!(cached.diff.churn.files()[0].path(), "src/main.rs");
+        assert_eq!(cached.diff.hunks[0].len(), 1);
+
+        // Entries of another version are dropped.
+        let database = Database::create(dir.path().join("stale.redb")).unwrap();
+        let cache = CommitCache::new(database).unwrap();
+        cache.insert(&[id], std::slice::from_ref(cached)).unwrap();
+        let transaction = cache.database.begin_write().unwrap();
+        transaction
+            .open_table(METADATA)
+            .unwrap()
+            .insert("version", CACHE_VERSION - 1)
+            .unwrap();
+        transaction.commit().unwrap();
+        let database = Arc::into_inner(cache.database).unwrap();
+        let cache = CommitCache::new(database).unwrap();
+        assert!(cache.get(&[id]).unwrap()[0].is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use git2::{Commit, Error, Oid, Repository, Revwalk};
+
+use crate::models::HistoryMode;
+
+use super::{BotDetector, PathFilter};
+
+/// Selects the commits of a range, and the files within them, that are analyzed.
+///
+/// The history mode decides how merge commits and the branches they bring in are treated, and
+/// commits authored by bots can optionally be left out as well.
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    mode: HistoryMode,
+    bots: Option<BotDetector>,
+    paths: PathFilter,
+}
+
+impl CommitFilter {
+    /// Creates a new `CommitFilter` selecting commits according to the given history mode.
+    pub fn new(mode: HistoryMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// Skips commits authored by bots matched by the given detector, if any.
+    pub fn with_excluded_bots(mut self, bots: Option<BotDetector>) -> Self {
+        self.bots = bots;
+        self
+    }
+
+    /// Only analyzes the files selected by the given path filter.
+    pub fn with_paths(mut self, paths: PathFilter) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// The filter selecting the files that are analyzed.
+    pub fn paths(&self) -> &PathFilter {
+        &self.paths
+    }
+
+    /// The detector for bots whose commits are skipped, if any.
+    pub fn bots(&self) -> Option<&BotDetector> {
+        self.bots.as_ref()
+    }
+
+    /// Returns whether only the first-parent chain of the range is walked.
+    ///
+    /// Merges-only mode also follows the first parent, so that merges of the main branch into a
+    /// feature branch do not count the main branch's changes a second time.
+    pub fn follows_first_parent(&self) -> bool {
+        matches!(self.mode, HistoryMode::FirstParent | HistoryMode::MergesOnly)
+    }
+
+    /// Returns whether a commit on the walked history is selected.
+    pub fn includes(&self, commit: &Commit) -> bool {
+        let selected = match self.mode {
+            HistoryMode::All | HistoryMode::FirstParent => true,
+            HistoryMode::NoMerges => commit.parent_count() <= 1,
+            HistoryMode::MergesOnly => commit.parent_count() > 1,
+        };
+        selected
+            && !self
+                .bots
+                .as_ref()
+                .is_some_and(|bots| bots.is_bot_signature(&commit.author()))
+    }
+
+    /// Walks the commits of a revwalk that are selected by the filter.
+    pub fn commits<'a>(
+        &'a self, repo: &'a Repository, mut revwalk: Revwalk<'a>,
+    ) -> Result<impl Iterator<Item = Result<Oid, Error>> + 'a, Error> {
+        if self.follows_first_parent() {
+            revwalk.simplify_first_parent()?;
+        }
+        Ok(revwalk.filter(move |id| match id {
+            Ok(id) => repo
+                .find_commit(*id)
+                .map_or(true, |commit| self.includes(&commit)),
+            Err(_) => true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_history_modes() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        let start = test_repo.commit("initial");
+
+        test_repo.write("feature.rs", "fn feature() {}\n");
+        let feature = test_repo.commit("feature");
+        test_repo.reset(start);
+        test_repo.write("main.rs", "fn main() {}\n");
+        let main = test_repo.commit("main");
+        let merge = test_repo.merge(feature, "merge feature");
+
+        let repo = Repository::open(test_repo.path()).unwrap();
+        let select = |mode| {
+            let filter = CommitFilter::new(mode);
+            let mut revwalk = repo.revwalk().unwrap();
+            revwalk.push(merge).unwrap();
+            revwalk.hide(start).unwrap();
+            let mut ids: Vec<Oid> = filter
+                .commits(&repo, revwalk)
+                .unwrap()
+                .collect::<Result<_, _>>()
+                .unwrap();
+            ids.sort();
+            ids
+        };
+        let sorted = |mut ids: Vec<Oid>| {
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(select(HistoryMode::All), sorted(vec![feature, main, merge]));
+        assert_eq!(select(HistoryMode::FirstParent), sorted(vec![main, merge]));
+        assert_eq!(select(HistoryMode::NoMerges), sorted(vec![feature, main]));
+        assert_eq!(select(HistoryMode::MergesOnly), vec![merge]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use git2::{Mailmap, Repository, Signature, Time};
+
+/// Resolves commit authors to canonical identities, keyed by email.
+///
+/// Signatures are first mapped through the repository's `.mailmap`, then through an optional
+/// alias map from names or emails to canonical emails, so that "Jane Doe", "jane" and
+/// "Jane Doe (work laptop)" count as one person. Each identity is displayed under the first name
+/// seen with its canonical email, or under the first alias name if it never appears directly.
+pub struct IdentityResolver {
+    mailmap: Mailmap,
+    aliases: HashMap<String, String>,
+    names: HashMap<String, (String, bool)>,
+    /// The canonical emails of the raw names and emails resolved so far.
+    resolved: HashMap<(String, String), String>,
+}
+
+impl IdentityResolver {
+    /// Creates a resolver using the repository's mailmap and the given aliases.
+    ///
+    /// Alias keys are matched case-insensitively against both names and emails.
+    pub fn new(repo: &Repository, aliases: &HashMap<String, String>) -> Result<Self, git2::Error> {
+        Ok(Self {
+            mailmap: repo.mailmap()?,
+            aliases: aliases
+                .iter()
+                .map(|(alias, email)| (alias.to_lowercase(), email.to_lowercase()))
+                .collect(),
+            names: HashMap::new(),
+            resolved: HashMap::new(),
+        })
+    }
+
+    /// Resolves a commit signature to the canonical email identifying its author.
+    pub fn resolve(&mut self, signature: &Signature) -> String {
+        match self.mailmap.resolve_signature(signature) {
+            Ok(mapped) => self.canonicalize(
+                mapped.name().unwrap_or("Unknown"),
+                mapped.email().unwrap_or_default(),
+            ),
+            Err(_) => self.canonicalize(
+                signature.name().unwrap_or("Unknown"),
+                signature.email().unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Resolves a raw author name and email to the canonical email identifying the author.
+    ///
+    /// Each distinct name and email is only looked up in the mailmap once.
+    pub fn resolve_parts(&mut self, name: &str, email: &str) -> String {
+        let key = (name.to_string(), email.to_string());
+        if let Some(canonical) = self.resolved.get(&key) {
+            return canonical.clone();
+        }
+        let canonical = match Signature::new(name, email, &Time::new(0, 0)) {
+            Ok(signature) => self.resolve(&signature),
+            // Signatures reject some unusual names, which can then only be matched by alias.
+            Err(_) => self.canonicalize(name, email),
+        };
+        self.resolved.insert(key, canonical.clone());
+        canonical
+    }
+
+    /// Resolves a name or email given by a user, such as a team member, to the canonical email
+    /// it stands for, without taking it as a display name.
+    pub fn resolve_member(&self, member: &str) -> String {
+        let (name, email) = if member.contains('@') {
+            ("Unknown", member)
+        } else {
+            (member, "")
+        };
+        match Signature::new(name, email, &Time::new(0, 0))
+            .and_then(|signature| self.mailmap.resolve_signature(&signature))
+        {
+            Ok(mapped) => self
+                .canonical_email(mapped.name().unwrap_or(name), mapped.email().unwrap_or_default()),
+            Err(_) => self.canonical_email(name, email),
+        }
+    }
+
+    /// Returns the display name of a canonical email returned by this resolver.
+    pub fn name<'a>(&'a self, email: &'a str) -> &'a str {
+        self.names
+            .get(email)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or(email)
+    }
+
+    /// Maps a name and email through the aliases to the canonical email.
+    fn canonical_email(&self, name: &str, email: &str) -> String {
+        let email = email.to_lowercase();
+        [&email, &name.to_lowercase()]
+            .iter()
+            .find_map(|key| self.aliases.get(*key).cloned())
+            // Without an email, fall back to the name as the identity key.
+            .unwrap_or_else(|| {
+                if email.is_empty() {
+                    name.to_lowercase()
+                } else {
+                    email.clone()
+                }
+            })
+    }
+
+    fn canonicalize(&mut self, name: &str, email: &str) -> String {
+        let email = email.to_lowercase();
+        let canonical = self.canonical_email(name, &email);
+
+        let direct = canonical == email;
+        match self.names.get_mut(&canonical) {
+            Some(entry) if direct && !entry.1 => *entry = (name.to_string(), true),
+            Some(_) => {}
+            None => {
+                self.names
+                    .insert(canonical.clone(), (name.to_string(), direct));
+            }
+        }
+        canonical
+    }
+}
+
+/// Returns the name and email of every co-author credited with a `Co-authored-by:` trailer.
+pub fn co_authors(message: &str) -> Vec<(String, String)> {
+    let Ok(trailers) = git2::message_trailers_strs(message) else {
+        return Vec::new();
+    };
+
+    trailers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("Co-authored-by"))
+        .map(|(_, value)| match value.rsplit_once('<') {
+            Some((name, email)) => {
+                (name.trim().to_string(), email.trim().trim_end_matches('>').trim().to_string())
+            }
+            None => (value.trim().to_string(), String::new()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_with_mailmap_and_aliases() {
+        let test_repo = TestRepo::new();
+        test_repo.write(".mailmap", "Jane Doe <jane@example.com> <jane@laptop.local>\n");
+        test_repo.commit("add mailmap");
+
+        let aliases = HashMap::from([
+            ("JD@work.example".to_string(), "Jane@Example.com".to_string()),
+            ("jdoe".to_string(), "jane@example.com".to_string()),
+        ]);
+        let mut resolver = IdentityResolver::new(&test_repo.repo, &aliases).unwrap();
+
+        for (name, email) in [
+            ("Jane Doe (work laptop)", "jd@work.example"),
+            ("jane", "jane@laptop.local"),
+            ("jdoe", "jdoe@home.example"),
+            ("Jane Doe", "Jane@Example.com"),
+        ] {
+            assert_eq!(resolver.resolve_parts(name, email), "jane@example.com");
+        }
+        assert_eq!(resolver.name("jane@example.com"), "Jane Doe");
+        assert_eq!(resolver.resolve_parts("John Roe", "John@Example.com"), "john@example.com");
+        assert_eq!(resolver.name("john@example.com"), "John Roe");
+    }
+
+    #[test]
+    fn test_co_authors_from_trailers() {
+        let message = "Pair on the parser\n\nSome details.\n\n\
+            Co-authored-by: John Roe <john@example.com>\n\
+            co-authored-by: Ann Smith <ann@example.com>\n\
+            Signed-off-by: Jane Doe <jane@example.com>\n";
+
+        assert_eq!(
+            co_authors(message),
+            [
+                ("John Roe".to_string(), "john@example.com".to_string()),
+                ("Ann Smith".to_string(), "ann@example.com".to_string()),
+            ]
+        );
+        assert!(co_authors("No trailers here\n").is_empty());
+    }
+}
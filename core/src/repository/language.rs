@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{Repository, Tree};
+
+use super::GitAttributes;
+
+/// Languages of files recognized by their exact name.
+const FILENAMES: &[(&str, &str)] = &[
+    ("BUILD", "Starlark"),
+    ("BUILD.bazel", "Starlark"),
+    ("WORKSPACE", "Starlark"),
+    ("CMakeLists.txt", "CMake"),
+    ("Dockerfile", "Dockerfile"),
+    ("Gemfile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+    ("Justfile", "Just"),
+    ("Makefile", "Makefile"),
+    ("Rakefile", "Ruby"),
+];
+
+/// Languages of files recognized by their extension.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("bash", "Shell"),
+    ("bzl", "Starlark"),
+    ("c", "C"),
+    ("cc", "C++"),
+    ("cjs", "JavaScript"),
+    ("clj", "Clojure"),
+    ("cpp", "C++"),
+    ("cs", "C#"),
+    ("css", "CSS"),
+    ("cxx", "C++"),
+    ("dart", "Dart"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("go", "Go"),
+    ("graphql", "GraphQL"),
+    ("h", "C"),
+    ("hbs", "Handlebars"),
+    ("hpp", "C++"),
+    ("hs", "Haskell"),
+    ("htm", "HTML"),
+    ("html", "HTML"),
+    ("java", "Java"),
+    ("jl", "Julia"),
+    ("js", "JavaScript"),
+    ("json", "JSON"),
+    ("jsx", "JavaScript"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("lua", "Lua"),
+    ("md", "Markdown"),
+    ("mjs", "JavaScript"),
+    ("ml", "OCaml"),
+    ("nix", "Nix"),
+    ("php", "PHP"),
+    ("pl", "Perl"),
+    ("proto", "Protocol Buffer"),
+    ("ps1", "PowerShell"),
+    ("py", "Python"),
+    ("r", "R"),
+    ("rb", "Ruby"),
+    ("rs", "Rust"),
+    ("scala", "Scala"),
+    ("scss", "SCSS"),
+    ("sh", "Shell"),
+    ("sql", "SQL"),
+    ("svelte", "Svelte"),
+    ("swift", "Swift"),
+    ("tf", "HCL"),
+    ("toml", "TOML"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("vue", "Vue"),
+    ("xml", "XML"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("zig", "Zig"),
+    ("zsh", "Shell"),
+];
+
+/// Languages of scripts recognized by the interpreter in their shebang line.
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("bash", "Shell"),
+    ("deno", "TypeScript"),
+    ("lua", "Lua"),
+    ("node", "JavaScript"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("python", "Python"),
+    ("ruby", "Ruby"),
+    ("sh", "Shell"),
+    ("ts-node", "TypeScript"),
+    ("zsh", "Shell"),
+];
+
+/// Classifies files by programming language.
+///
+/// A `linguist-language` attribute in `.gitattributes` takes precedence. Otherwise files are
+/// recognized by their name or extension, and files without either by their shebang line.
+/// Results are cached per path.
+pub struct LanguageDetector {
+    attributes: GitAttributes,
+    languages: HashMap<String, Option<String>>,
+}
+
+impl LanguageDetector {
+    /// Creates a new `LanguageDetector` honouring the given attributes.
+    pub fn new(attributes: GitAttributes) -> Self {
+        Self {
+            attributes,
+            languages: HashMap::new(),
+        }
+    }
+
+    /// Returns the language of a file, reading its shebang line from `tree` when needed.
+    pub fn detect(&mut self, repo: &Repository, tree: &Tree, path: &str) -> Option<&str> {
+        if !self.languages.contains_key(path) {
+            let language = self
+                .attributes
+                .get(path, "linguist-language")
+                .map(str::to_string)
+                .or_else(|| language_from_name(path).map(str::to_string))
+                .or_else(|| language_from_shebang(repo, tree, path).map(str::to_string));
+            self.languages.insert(path.to_string(), language);
+        }
+        self.languages[path].as_deref()
+    }
+}
+
+fn language_from_name(path: &str) -> Option<&'static str> {
+    let path = Path::new(path);
+    let name = path.file_name()?.to_str()?;
+    if let Some((_, language)) = FILENAMES.iter().find(|(filename, _)| *filename == name) {
+        return Some(language);
+    }
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, language)| *language)
+}
+
+/// Recognizes scripts such as `#!/usr/bin/env python3` or `#!/bin/bash`.
+fn language_from_shebang(repo: &Repository, tree: &Tree, path: &str) -> Option<&'static str> {
+    let blob = repo
+        .find_blob(tree.get_path(Path::new(path)).ok()?.id())
+        .ok()?;
+    let first_line = blob.content().split(|byte| *byte == b'\n').next()?;
+    let shebang = std::str::from_utf8(first_line).ok()?.strip_prefix("#!")?;
+
+    let mut words = shebang.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-'))?;
+    }
+    // Versioned interpreters like `python3.12` are named after their unversioned command.
+    let interpreter = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|(known, _)| *known == interpreter)
+        .map(|(_, language)| *language)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        let test_repo = TestRepo::new();
+        test_repo.write(".gitattributes", "*.inc linguist-language=PHP\n");
+        test_repo.write("bin/release", "#!/usr/bin/env python3\nprint('hi')\n");
+        test_repo.write("bin/setup", "#!/bin/sh -e\necho hi\n");
+        test_repo.write("LICENSE", "MIT\n");
+        let commit = test_repo.commit("initial");
+        let commit = test_repo.repo.find_commit(commit).unwrap();
+        let tree = commit.tree().unwrap();
+
+        let attributes = GitAttributes::load(&test_repo.repo, &commit).unwrap();
+        let mut detector = LanguageDetector::new(attributes);
+        let mut detect = |path| {
+            detector
+                .detect(&test_repo.repo, &tree, path)
+                .map(str::to_string)
+        };
+
+        assert_eq!(detect("src/main.rs").as_deref(), Some("Rust"));
+        assert_eq!(detect("web/App.TSX").as_deref(), Some("TypeScript"));
+        assert_eq!(detect("tools/BUILD.bazel").as_deref(), Some("Starlark"));
+        assert_eq!(detect("lib/header.inc").as_deref(), Some("PHP"));
+        assert_eq!(detect("bin/release").as_deref(), Some("Python"));
+        assert_eq!(detect("bin/setup").as_deref(), Some("Shell"));
+        assert_eq!(detect("LICENSE"), None);
+    }
+}
@@ -1,43 +1,184 @@
-use git2::{DiffOptions, Error, Repository, Revwalk};
-
-use crate::models::CodeChurn;
-
-pub fn create_revwalk<'a>(
-    repo: &'a Repository, old_commit: &'a str, new_commit: &'a str,
-) -> Result<Revwalk<'a>, Error> {
-    let old_commit = repo.revparse_single(old_commit)?.peel_to_commit()?;
-    let new_commit = repo.revparse_single(new_commit)?.peel_to_commit()?;
-    let mut revwalk = repo.revwalk()?;
-    revwalk.push(new_commit.id())?;
-    revwalk.hide(old_commit.id())?;
-    revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
-    Ok(revwalk)
+use git2::{Commit, Diff, DiffFindOptions, DiffOptions, Error, Patch, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{CodeChurn, FileChurn, FileStatus};
+
+pub use attributes::GitAttributes;
+pub use bots::BotDetector;
+pub use cache::{CachedCommit, CommitCache};
+pub use filter::CommitFilter;
+pub use identity::{co_authors, IdentityResolver};
+pub use language::LanguageDetector;
+pub use paths::{PathFilter, PathMatcher};
+pub use range::CommitRange;
+pub use test_files::TestClassifier;
+
+mod attributes;
+mod bots;
+mod cache;
+mod filter;
+mod identity;
+mod language;
+mod paths;
+mod range;
+mod test_files;
+
+/// A hunk of a diff without context lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hunk {
+    /// The first removed line of the old file, counting from 1, or the line after which the new
+    /// lines are inserted when none are removed.
+    pub old_start: usize,
+    pub old_lines: usize,
+    /// The first added line of the new file, counting from 1, or the line after which the old
+    /// lines were removed when none are added.
+    pub new_start: usize,
+    pub new_lines: usize,
 }
 
-pub fn calculate_code_churn(repo: &Repository, revwalk: Revwalk) -> Result<Vec<CodeChurn>, Error> {
-    let mut churn_data = Vec::new();
-
-    for id in revwalk {
-        let commit = repo.find_commit(id?)?;
-        let tree = commit.tree()?;
-        let parent_tree = if let Ok(parent) = commit.parent(0) {
-            parent.tree()?
-        } else {
-            repo.treebuilder(None)?
-                .write()
-                .map(|oid| repo.find_tree(oid))?? // Create an empty tree if no parent
+/// The diff of a commit against its first parent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDiff {
+    pub churn: CodeChurn,
+    /// The hunks of each file of the churn, in the same order. Binary files have none.
+    pub hunks: Vec<Vec<Hunk>>,
+}
+
+/// Diffs a commit against its first parent, or against the empty tree for root commits.
+pub fn diff_commit(repo: &Repository, commit: &Commit) -> Result<CommitDiff, Error> {
+    let tree = commit.tree()?;
+    let parent_tree = if let Ok(parent) = commit.parent(0) {
+        parent.tree()?
+    } else {
+        repo.treebuilder(None)?
+            .write()
+            .map(|oid| repo.find_tree(oid))?? // Create an empty tree if no parent
+    };
+
+    let mut diff = repo.diff_tree_to_tree(
+        Some(&parent_tree),
+        Some(&tree),
+        Some(DiffOptions::new().context_lines(0)),
+    )?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    let stats = diff.stats()?;
+    let commit_id = commit.id().to_string();
+    let (files, hunks) = calculate_file_churn(&diff, &commit_id)?;
+
+    Ok(CommitDiff {
+        churn: CodeChurn::new(commit_id, stats.insertions(), stats.deletions(), files),
+        hunks,
+    })
+}
+
+/// Breaks a commit diff down into the churn and hunks of each file it touches.
+fn calculate_file_churn(
+    diff: &Diff, commit_id: &str,
+) -> Result<(Vec<FileChurn>, Vec<Vec<Hunk>>), Error> {
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    let mut hunks = Vec::with_capacity(diff.deltas().len());
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        // A patch is only produced for text files; binary files have no line stats.
+        let patch = Patch::from_diff(diff, idx)?;
+        let (additions, deletions) = match &patch {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            None => (0, 0),
         };
+        let binary = patch.is_none() || delta.flags().is_binary();
+        let mut file_hunks = Vec::new();
+        if let Some(patch) = &patch {
+            for hunk_idx in 0..patch.num_hunks() {
+                let (hunk, _) = patch.hunk(hunk_idx)?;
+                file_hunks.push(Hunk {
+                    old_start: hunk.old_start() as usize,
+                    old_lines: hunk.old_lines() as usize,
+                    new_start: hunk.new_start() as usize,
+                    new_lines: hunk.new_lines() as usize,
+                });
+            }
+        }
 
-        let diff =
-            repo.diff_tree_to_tree(Some(&parent_tree), Some(&tree), Some(&mut DiffOptions::new()))?;
-        let stats = diff.stats()?;
+        let path_of = |file: git2::DiffFile| {
+            file.path()
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let path = path_of(delta.new_file());
+        let old_path = Some(path_of(delta.old_file())).filter(|old_path| *old_path != path);
 
-        churn_data.push(CodeChurn::new(
-            commit.id().to_string(),
-            stats.insertions(),
-            stats.deletions(),
+        files.push(FileChurn::new(
+            commit_id.to_string(),
+            path,
+            old_path,
+            FileStatus::from(delta.status()),
+            additions,
+            deletions,
+            binary,
         ));
+        hunks.push(file_hunks);
     }
 
-    Ok(churn_data)
+    Ok((files, hunks))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_commit_per_file() {
+        let test_repo = TestRepo::new();
+
+        test_repo.write("main.rs", "fn main() {}\n");
+        test_repo.write("notes.txt", "one\ntwo\nthree\nfour\nfive\n");
+        test_repo.commit("initial");
+
+        test_repo.write("main.rs", "fn main() {\n    println!(\"hi\");\n}\n");
+        test_repo.rename("notes.txt", "README.txt");
+        test_repo.write("logo.bin", [0u8, 159, 146, 150, 0, 1, 2]);
+        let second = test_repo.commit("second");
+
+        let commit = test_repo.repo.find_commit(second).unwrap();
+        let diff = diff_commit(&test_repo.repo, &commit).unwrap();
+        let churn = &diff.churn;
+        assert_eq!(churn.commit(), second.to_string());
+        assert_eq!((churn.additions(), churn.deletions()), (3, 1));
+
+        let position = |path: &str| {
+            churn
+                .files()
+                .iter()
+                .position(|file| file.path() == path)
+                .unwrap()
+        };
+        let file = |path: &str| &churn.files()[position(path)];
+
+        let main = file("main.rs");
+        assert_eq!(main.status(), FileStatus::Modified);
+        assert_eq!((main.additions(), main.deletions()), (3, 1));
+        assert!(!main.is_binary());
+        let hunk = Hunk {
+            old_start: 1,
+            old_lines: 1,
+            new_start: 1,
+            new_lines: 3,
+        };
+        assert_eq!(diff.hunks[position("main.rs")], [hunk]);
+
+        let readme = file("README.txt");
+        assert_eq!(readme.status(), FileStatus::Renamed);
+        assert_eq!(readme.old_path(), Some("notes.txt"));
+        assert_eq!(readme.total_changes(), 0);
+
+        let logo = file("logo.bin");
+        assert_eq!(logo.status(), FileStatus::Added);
+        assert!(logo.is_binary());
+        assert!(diff.hunks[position("logo.bin")].is_empty());
+    }
 }
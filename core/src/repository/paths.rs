@@ -0,0 +1,173 @@
+use std::sync::OnceLock;
+
+use git2::{Commit, Error, Repository};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use super::GitAttributes;
+
+/// Lockfiles, which are rewritten by package managers rather than by hand.
+const LOCKFILE_PATTERNS: &[&str] = &[
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/npm-shrinkwrap.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/Gemfile.lock",
+    "**/Pipfile.lock",
+    "**/poetry.lock",
+    "**/uv.lock",
+    "**/composer.lock",
+    "**/go.sum",
+    "**/flake.lock",
+];
+
+/// Well-known generated files, on top of those marked with `linguist-generated`.
+const GENERATED_PATTERNS: &[&str] = &[
+    "**/*.min.js",
+    "**/*.min.css",
+    "**/*.js.map",
+    "**/*.pb.go",
+    "**/*_pb2.py",
+];
+
+/// Well-known vendored directories, on top of those marked with `linguist-vendored`.
+const VENDORED_PATTERNS: &[&str] = &["**/vendor/**", "**/node_modules/**", "**/third_party/**"];
+
+/// Selects the file paths that are analyzed.
+///
+/// A path is kept when it matches one of the include globs, or when there are none, and does
+/// not match any exclude glob. Globs use `*` within a single path component and `**` across
+/// components.
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    exclude_generated: bool,
+}
+
+impl PathFilter {
+    /// Creates a new `PathFilter` from include and exclude globs.
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, globset::Error> {
+        let set = |globs: &[String]| -> Result<Option<GlobSet>, globset::Error> {
+            if globs.is_empty() {
+                return Ok(None);
+            }
+            glob_set(globs.iter().map(String::as_str)).map(Some)
+        };
+        Ok(Self {
+            include: set(include)?,
+            exclude: set(exclude)?,
+            exclude_generated: false,
+        })
+    }
+
+    /// Also excludes lockfiles and vendored or generated files.
+    ///
+    /// Files count as vendored or generated when marked with the `linguist-vendored` or
+    /// `linguist-generated` attributes, or when their path is a well-known one that is not
+    /// explicitly unmarked, like `linguist` does.
+    pub fn with_generated_excluded(mut self, exclude_generated: bool) -> Self {
+        self.exclude_generated = exclude_generated;
+        self
+    }
+
+    /// Resolves the filter against the `.gitattributes` files of a commit.
+    pub fn at(&self, repo: &Repository, commit: &Commit) -> Result<PathMatcher<'_>, Error> {
+        let attributes = if self.exclude_generated {
+            GitAttributes::load(repo, commit)?
+        } else {
+            GitAttributes::default()
+        };
+        Ok(PathMatcher {
+            filter: self,
+            attributes,
+        })
+    }
+}
+
+/// A `PathFilter` resolved against the attributes of a commit.
+pub struct PathMatcher<'a> {
+    filter: &'a PathFilter,
+    attributes: GitAttributes,
+}
+
+impl PathMatcher<'_> {
+    /// Returns whether a path is analyzed.
+    pub fn includes(&self, path: &str) -> bool {
+        let filter = self.filter;
+        if filter
+            .include
+            .as_ref()
+            .is_some_and(|set| !set.is_match(path))
+            || filter
+                .exclude
+                .as_ref()
+                .is_some_and(|set| set.is_match(path))
+        {
+            return false;
+        }
+        !filter.exclude_generated || !self.is_generated_or_vendored(path)
+    }
+
+    fn is_generated_or_vendored(&self, path: &str) -> bool {
+        static GENERATED: OnceLock<GlobSet> = OnceLock::new();
+        static VENDORED: OnceLock<GlobSet> = OnceLock::new();
+        let generated = GENERATED.get_or_init(|| {
+            glob_set(LOCKFILE_PATTERNS.iter().chain(GENERATED_PATTERNS).copied()).unwrap()
+        });
+        let vendored =
+            VENDORED.get_or_init(|| glob_set(VENDORED_PATTERNS.iter().copied()).unwrap());
+
+        self.attributes
+            .is_set(path, "linguist-generated")
+            .unwrap_or_else(|| generated.is_match(path))
+            || self
+                .attributes
+                .is_set(path, "linguist-vendored")
+                .unwrap_or_else(|| vendored.is_match(path))
+    }
+}
+
+pub(super) fn glob_set<'a>(
+    globs: impl IntoIterator<Item = &'a str>,
+) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(GlobBuilder::new(glob).literal_separator(true).build()?);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_path_filter() {
+        let test_repo = TestRepo::new();
+        test_repo.write(
+            ".gitattributes",
+            "schema/*.json linguist-generated\nvendor/** -linguist-vendored\n",
+        );
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        let commit = test_repo.commit("initial");
+        let commit = test_repo.repo.find_commit(commit).unwrap();
+
+        let filter = PathFilter::new(&["src/**".to_string()], &["src/gen/**".to_string()]).unwrap();
+        let paths = filter.at(&test_repo.repo, &commit).unwrap();
+        assert!(paths.includes("src/main.rs"));
+        assert!(!paths.includes("src/gen/api.rs"));
+        assert!(!paths.includes("docs/index.md"));
+
+        let filter = PathFilter::default().with_generated_excluded(true);
+        let paths = filter.at(&test_repo.repo, &commit).unwrap();
+        assert!(paths.includes("src/main.rs"));
+        assert!(!paths.includes("Cargo.lock"));
+        assert!(!paths.includes("web/package-lock.json"));
+        assert!(!paths.includes("schema/api.json"));
+        assert!(!paths.includes("web/node_modules/left-pad/index.js"));
+        assert!(paths.includes("vendor/patched/lib.rs"));
+    }
+}
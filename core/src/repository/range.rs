@@ -0,0 +1,362 @@
+use std::error::Error;
+
+use chrono::{DateTime, NaiveDate};
+use git2::{Object, Oid, Repository, RevparseMode, Sort};
+
+use crate::models::{RangeError, RangeSpec};
+
+/// The commits of a range, resolved against a repository.
+#[derive(Debug, Clone)]
+pub struct CommitRange {
+    /// The revision the range starts after, as given, or `None` for the whole history. A range
+    /// given by a revspec or the latest tags keeps the side of the revspec or the tag name.
+    pub start_commit: Option<String>,
+    /// The revision the range ends at, as given, or `HEAD` by default.
+    pub end_commit: String,
+    /// The commit the range ends at.
+    pub end: Oid,
+    /// The commits of the range, newest first.
+    pub commits: Vec<Oid>,
+}
+
+impl CommitRange {
+    /// Resolves a range spec into its commits, following only the first parent of merges when
+    /// asked to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RangeError` naming the offending token if the spec is invalid, or a
+    /// `git2::Error` if the repository cannot be walked.
+    pub fn resolve(
+        repo: &Repository, spec: &RangeSpec, first_parent: bool,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let (start, (end_commit, end)) = ends(repo, spec)?;
+        let since = spec
+            .since
+            .as_deref()
+            .map(|since| parse_time(since, false))
+            .transpose()?;
+        let until = spec
+            .until
+            .as_deref()
+            .map(|until| parse_time(until, true))
+            .transpose()?;
+
+        // One commit past `max_commits` is enough to reject the range.
+        let limit = match (spec.last, spec.max_commits) {
+            (Some(last), Some(max_commits)) => Some(last.min(max_commits + 1)),
+            (last, max_commits) => last.or(max_commits.map(|max_commits| max_commits + 1)),
+        };
+        let dated = |id: Oid| -> Result<bool, git2::Error> {
+            if since.is_none() && until.is_none() {
+                return Ok(true);
+            }
+            let time = repo.find_commit(id)?.time().seconds();
+            Ok(since.is_none_or(|since| time >= since) && until.is_none_or(|until| time <= until))
+        };
+        let commits =
+            walk(repo, start.as_ref().map(|(_, start)| *start), end, first_parent, limit, dated)?;
+        if let Some(max_commits) = spec.max_commits {
+            if commits.len() > max_commits {
+                return Err(RangeError::TooManyCommits(max_commits).into());
+            }
+        }
+
+        Ok(Self {
+            start_commit: start.map(|(start_commit, _)| start_commit),
+            end_commit,
+            end,
+            commits,
+        })
+    }
+}
+
+/// A revision of a range, as given, and the commit it names.
+type End = (String, Oid);
+
+/// Resolves the revisions a range spec starts after, if any, and ends at.
+fn ends(
+    repo: &Repository, spec: &RangeSpec,
+) -> Result<(Option<End>, End), Box<dyn Error + Send + Sync>> {
+    // A revspec or the latest tags give both ends, so they cannot be combined with anything
+    // else giving an end.
+    let given: Vec<&'static str> = [
+        ("revspec", spec.revspec.is_some()),
+        ("between_latest_tags", spec.between_latest_tags),
+        ("start_commit", spec.start_commit.is_some()),
+        ("end_commit", spec.end_commit.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, set)| set.then_some(field))
+    .collect();
+    if let [first @ ("revspec" | "between_latest_tags"), second, ..] = given[..] {
+        return Err(RangeError::Conflict(first, second).into());
+    }
+
+    if let Some(revspec) = &spec.revspec {
+        return Ok(parse_revspec(repo, revspec)?);
+    }
+    if spec.between_latest_tags {
+        return latest_tags(repo);
+    }
+    let end = revision(repo, spec.end_commit.as_deref().unwrap_or("HEAD"))?;
+    let start = spec
+        .start_commit
+        .as_deref()
+        .map(|start| revision(repo, start))
+        .transpose()?;
+    Ok((start, end))
+}
+
+/// Resolves a single revision to the commit it names.
+fn revision(repo: &Repository, revision: &str) -> Result<End, RangeError> {
+    repo.revparse_single(revision)
+        .and_then(|object| object.peel_to_commit())
+        .map(|commit| (revision.to_string(), commit.id()))
+        .map_err(|_| RangeError::InvalidRevision(revision.to_string()))
+}
+
+/// Resolves a revspec, either a range such as `v1.2.0..main` or a single revision.
+fn parse_revspec(repo: &Repository, revspec: &str) -> Result<(Option<End>, End), RangeError> {
+    let parsed = repo
+        .revparse(revspec)
+        .map_err(|_| RangeError::InvalidRevision(offending_revision(repo, revspec)))?;
+    if parsed.mode().contains(RevparseMode::MERGE_BASE) {
+        return Err(RangeError::SymmetricDifference(revspec.to_string()));
+    }
+    let peel = |object: Option<&Object>| {
+        object
+            .and_then(|object| object.peel_to_commit().ok())
+            .map(|commit| commit.id())
+            .ok_or_else(|| RangeError::InvalidRevision(revspec.to_string()))
+    };
+    match revspec.split_once("..") {
+        Some((from, to)) if parsed.mode().contains(RevparseMode::RANGE) => {
+            // An empty side stands for `HEAD`, as it does for git.
+            let side = |side: &str| if side.is_empty() { "HEAD" } else { side }.to_string();
+            Ok((Some((side(from), peel(parsed.from())?)), (side(to), peel(parsed.to())?)))
+        }
+        _ => Ok((None, (revspec.to_string(), peel(parsed.from())?))),
+    }
+}
+
+/// Finds the side of a revspec that does not parse, or the whole revspec if neither side is to
+/// blame.
+fn offending_revision(repo: &Repository, revspec: &str) -> String {
+    let sides = revspec
+        .split_once("...")
+        .or_else(|| revspec.split_once(".."));
+    sides
+        .and_then(|(from, to)| {
+            [from, to]
+                .into_iter()
+                .find(|side| !side.is_empty() && repo.revparse_single(side).is_err())
+        })
+        .unwrap_or(revspec)
+        .to_string()
+}
+
+/// Finds the two most recent tags, by commit time.
+fn latest_tags(repo: &Repository) -> Result<(Option<End>, End), Box<dyn Error + Send + Sync>> {
+    let mut tagged: Vec<(i64, Oid, String)> = repo
+        .tag_names(None)?
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            // Tags of trees or blobs do not tag a commit.
+            let commit = repo
+                .revparse_single(&format!("refs/tags/{}", name))
+                .and_then(|object| object.peel_to_commit())
+                .ok()?;
+            Some((commit.time().seconds(), commit.id(), name.to_string()))
+        })
+        .collect();
+    tagged.sort_unstable_by(|a, b| b.cmp(a));
+    tagged.dedup_by_key(|(time, id, _)| (*time, *id));
+
+    let found = tagged.len();
+    let mut tagged = tagged.into_iter().map(|(_, id, name)| (name, id));
+    match (tagged.next(), tagged.next()) {
+        (Some(end), Some(start)) => Ok((Some(start), end)),
+        _ => Err(RangeError::NotEnoughTags(found).into()),
+    }
+}
+
+/// Parses an RFC 3339 timestamp or a `YYYY-MM-DD` date in UTC into seconds since the epoch. A
+/// date stands for its first second, or for its last one at the end of a range.
+fn parse_time(text: &str, end_of_day: bool) -> Result<i64, RangeError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.timestamp());
+    }
+    let date = NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| RangeError::InvalidTimestamp(text.to_string()))?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.unwrap_or_default().and_utc().timestamp())
+}
+
+/// Lists the commits after `start` up to `end` that are kept, newest first, stopping once
+/// `limit` commits are kept.
+fn walk(
+    repo: &Repository, start: Option<Oid>, end: Oid, first_parent: bool, limit: Option<usize>,
+    mut keep: impl FnMut(Oid) -> Result<bool, git2::Error>,
+) -> Result<Vec<Oid>, git2::Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(end)?;
+    if let Some(start) = start {
+        revwalk.hide(start)?;
+    }
+    revwalk.set_sorting(Sort::TOPOLOGICAL)?;
+    if first_parent {
+        revwalk.simplify_first_parent()?;
+    }
+
+    let mut commits = Vec::new();
+    for id in revwalk {
+        if limit.is_some_and(|limit| commits.len() >= limit) {
+            break;
+        }
+        let id = id?;
+        if keep(id)? {
+            commits.push(id);
+        }
+    }
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Time;
+
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    fn tag(test_repo: &TestRepo, name: &str, id: Oid) {
+        let object = test_repo.repo.find_object(id, None).unwrap();
+        test_repo
+            .repo
+            .tag_lightweight(name, &object, false)
+            .unwrap();
+    }
+
+    fn resolve(test_repo: &TestRepo, spec: RangeSpec) -> Result<CommitRange, String> {
+        CommitRange::resolve(&test_repo.repo, &spec, false).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_resolve_range_specs() {
+        let test_repo = TestRepo::new();
+        let day = 24 * 60 * 60;
+        let ids: Vec<Oid> = (0..5)
+            .map(|n| {
+                test_repo.write("README.md", format!("{}\n", n));
+                // 2023-11-14 onwards, one commit a day.
+                test_repo.commit_as(
+                    "Jane Doe",
+                    "jane@example.com",
+                    Time::new(1_700_000_000 + n * day, 0),
+                    &format!("commit {}", n),
+                )
+            })
+            .collect();
+        tag(&test_repo, "v1.0.0", ids[1]);
+        tag(&test_repo, "v1.1.0", ids[3]);
+
+        let range = resolve(&test_repo, RangeSpec::default()).unwrap();
+        assert_eq!(range.commits.len(), 5);
+        assert_eq!(range.start_commit, None);
+
+        let revspec = |revspec: &str| RangeSpec {
+            revspec: Some(revspec.to_string()),
+            ..RangeSpec::default()
+        };
+        let range = resolve(&test_repo, revspec("v1.0.0..HEAD~1")).unwrap();
+        assert_eq!(range.commits, [ids[3], ids[2]]);
+        assert_eq!(range.start_commit.as_deref(), Some("v1.0.0"));
+        assert_eq!(range.end_commit, "HEAD~1");
+        let range = resolve(&test_repo, revspec("HEAD~3")).unwrap();
+        assert_eq!(range.commits, [ids[1], ids[0]]);
+
+        let range = resolve(
+            &test_repo,
+            RangeSpec {
+                between_latest_tags: true,
+                ..RangeSpec::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(range.commits, [ids[3], ids[2]]);
+        assert_eq!(range.start_commit.as_deref(), Some("v1.0.0"));
+        assert_eq!(range.end_commit, "v1.1.0");
+
+        let range = resolve(
+            &test_repo,
+            RangeSpec {
+                since: Some("2023-11-15".to_string()),
+                until: Some("2023-11-17T00:00:00Z".to_string()),
+                last: Some(2),
+                ..RangeSpec::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(range.commits, [ids[2], ids[1]]);
+
+        assert_eq!(
+            resolve(&test_repo, revspec("v1.0.0..mian")).unwrap_err(),
+            "Invalid revision: mian"
+        );
+        assert_eq!(
+            resolve(&test_repo, revspec("v1.0.0...HEAD")).unwrap_err(),
+            "Unsupported revspec: v1.0.0...HEAD, symmetric differences are not supported"
+        );
+        let error = resolve(
+            &test_repo,
+            RangeSpec {
+                since: Some("last tuesday".to_string()),
+                ..RangeSpec::default()
+            },
+        );
+        assert_eq!(error.unwrap_err(), "Invalid timestamp: last tuesday");
+        let error = resolve(
+            &test_repo,
+            RangeSpec {
+                end_commit: Some("main".to_string()),
+                ..revspec("v1.0.0..HEAD")
+            },
+        );
+        assert_eq!(
+            error.unwrap_err(),
+            "Conflicting range fields: revspec cannot be combined with end_commit"
+        );
+        let error = CommitRange::resolve(
+            &test_repo.repo,
+            &RangeSpec {
+                max_commits: Some(4),
+                ..RangeSpec::default()
+            },
+            false,
+        );
+        assert!(error.unwrap_err().is::<RangeError>());
+        let range = resolve(
+            &test_repo,
+            RangeSpec {
+                max_commits: Some(5),
+                ..RangeSpec::default()
+            },
+        );
+        assert_eq!(range.unwrap().commits.len(), 5);
+        let range = resolve(
+            &test_repo,
+            RangeSpec {
+                last: Some(2),
+                max_commits: Some(2),
+                ..RangeSpec::default()
+            },
+        );
+        assert_eq!(range.unwrap().commits, [ids[4], ids[3]]);
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use git2::{Repository, Tree};
+use globset::GlobSet;
+
+use super::paths::glob_set;
+
+/// The paths and names of test files by language, following the conventions of their
+/// ecosystems.
+const TEST_PATTERNS: &[(&str, &[&str])] = &[
+    ("C", &["**/test/**", "**/tests/**", "**/*_test.c", "**/test_*.c"]),
+    ("C#", &["**/*.Tests/**", "**/*Test.cs", "**/*Tests.cs"]),
+    (
+        "C++",
+        &[
+            "**/test/**",
+            "**/tests/**",
+            "**/*_test.cc",
+            "**/*_test.cpp",
+            "**/*_unittest.cc",
+        ],
+    ),
+    ("Dart", &["**/test/**", "**/*_test.dart"]),
+    ("Elixir", &["**/test/**", "**/*_test.exs"]),
+    ("Go", &["**/*_test.go"]),
+    ("Java", &["**/src/test/**", "**/*Test.java", "**/*Tests.java"]),
+    (
+        "JavaScript",
+        &[
+            "**/__tests__/**",
+            "**/*.test.js",
+            "**/*.spec.js",
+            "**/*.test.jsx",
+            "**/*.spec.jsx",
+        ],
+    ),
+    ("Kotlin", &["**/src/test/**", "**/*Test.kt"]),
+    ("PHP", &["**/tests/**", "**/*Test.php"]),
+    (
+        "Python",
+        &[
+            "**/tests/**",
+            "**/test_*.py",
+            "**/*_test.py",
+            "**/conftest.py",
+        ],
+    ),
+    ("Ruby", &["**/spec/**", "**/test/**", "**/*_spec.rb", "**/*_test.rb"]),
+    ("Rust", &["**/tests/**", "**/benches/**"]),
+    ("Scala", &["**/src/test/**", "**/*Spec.scala", "**/*Test.scala"]),
+    ("Swift", &["**/Tests/**", "**/*Tests.swift"]),
+    (
+        "TypeScript",
+        &[
+            "**/__tests__/**",
+            "**/*.test.ts",
+            "**/*.spec.ts",
+            "**/*.test.tsx",
+            "**/*.spec.tsx",
+        ],
+    ),
+];
+
+/// The attribute opening the inline test module of a Rust file.
+const RUST_TEST_MODULE: &str = "#[cfg(test)]";
+
+/// Tells test code from production code.
+///
+/// Only files of a language with test conventions count as code, so that documentation and
+/// configuration are neither. Within Rust files outside of the test directories, everything
+/// from the first `#[cfg(test)]` attribute on is test code, as that is where the test module
+/// conventionally goes.
+pub struct TestClassifier {
+    patterns: HashMap<String, GlobSet>,
+}
+
+impl TestClassifier {
+    /// Creates a new `TestClassifier`, with the given globs replacing the conventions of their
+    /// languages.
+    pub fn new(overrides: &HashMap<String, Vec<String>>) -> Result<Self, globset::Error> {
+        let mut patterns = HashMap::new();
+        for (language, globs) in TEST_PATTERNS {
+            if !overrides.contains_key(*language) {
+                patterns.insert(language.to_string(), glob_set(globs.iter().copied())?);
+            }
+        }
+        for (language, globs) in overrides {
+            patterns.insert(language.clone(), glob_set(globs.iter().map(String::as_str))?);
+        }
+        Ok(Self { patterns })
+    }
+
+    /// Returns whether a file of the given language is a test file, or `None` when the
+    /// language has no test conventions.
+    pub fn is_test_file(&self, language: &str, path: &str) -> Option<bool> {
+        self.patterns
+            .get(language)
+            .map(|patterns| patterns.is_match(path))
+    }
+
+    /// Returns the line, counting from 1, from which a file of the given language in `tree`
+    /// is test code, if any.
+    pub fn test_module_start(
+        &self, repo: &Repository, tree: &Tree, language: &str, path: &str,
+    ) -> Option<usize> {
+        if language != "Rust" {
+            return None;
+        }
+        let blob = tree.get_path(path.as_ref()).ok()?.to_object(repo).ok()?;
+        let content = std::str::from_utf8(blob.as_blob()?.content()).ok()?;
+        content
+            .lines()
+            .position(|line| line.trim_start().starts_with(RUST_TEST_MODULE))
+            .map(|index| index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_classify_test_files() {
+        let overrides = HashMap::from([("Go".to_string(), vec!["**/testing/**".to_string()])]);
+        let classifier = TestClassifier::new(&overrides).unwrap();
+        assert_eq!(classifier.is_test_file("Rust", "tests/api.rs"), Some(true));
+        assert_eq!(classifier.is_test_file("Rust", "core/tests/api.rs"), Some(true));
+        assert_eq!(classifier.is_test_file("Rust", "src/lib.rs"), Some(false));
+        assert_eq!(classifier.is_test_file("TypeScript", "src/app.spec.ts"), Some(true));
+        assert_eq!(classifier.is_test_file("Python", "pkg/test_models.py"), Some(true));
+        assert_eq!(classifier.is_test_file("Go", "server_test.go"), Some(false));
+        assert_eq!(classifier.is_test_file("Go", "testing/server.go"), Some(true));
+        assert_eq!(classifier.is_test_file("Markdown", "tests/README.md"), None);
+
+        let test_repo = TestRepo::new();
+        test_repo.write("src/lib.rs", "fn a() {}\n\n#[cfg(test)]\nmod tests {}\n");
+        test_repo.write("src/main.rs", "fn main() {}\n");
+        test_repo.commit("initial");
+        let tree = test_repo.repo.head().unwrap().peel_to_tree().unwrap();
+        let start = |path| classifier.test_module_start(&test_repo.repo, &tree, "Rust", path);
+        assert_eq!(start("src/lib.rs"), Some(3));
+        assert_eq!(start("src/main.rs"), None);
+        assert_eq!(start("src/missing.rs"), None);
+    }
+}
@@ -1,16 +1,35 @@
 use std::error::Error;
 
-use crate::analyzers::{Analyzer, CodeChurnAnalyzer, TopContributorsAnalyzer};
-use crate::models::{CommitRangeAnalysis, CommitRangeDetails, Contributor, Protocol, Repository};
+use chrono_tz::Tz;
+
+use crate::analyzers::{
+    ActivityAnalyzer, Analyzer, BusFactorAnalyzer, CodeChurnAnalyzer, CommitVisitor,
+    ComplianceAnalyzer, CouplingAnalyzer, HotspotAnalyzer, LanguageAnalyzer, OutlierAnalyzer,
+    OwnershipAnalyzer, Pipeline, ReworkAnalyzer, TestRatioAnalyzer, TopContributorsAnalyzer,
+    WorkPatternAnalyzer,
+};
+use crate::models::{
+    AnalysisSection, CommitRangeAnalysis, CommitRangeDetails, CommitRangeOptions, OptionsError,
+    Protocol, RangeSpec, Repository,
+};
+use crate::repository::{
+    BotDetector, CommitCache, CommitFilter, CommitRange, PathFilter, TestClassifier,
+};
 use crate::utils::RepositoryManager;
 
+use super::run_blocking;
+
 /// Analyzes a specified range of commits within a repository using various analyzers.
 ///
+/// The history of the range is walked once on a blocking thread, with every analyzer of the
+/// requested sections looking at each commit. The diffs of commits are cached next to the
+/// clone, so repeated or overlapping ranges only diff the commits not seen before.
+///
 /// # Arguments
 ///
 /// * `repository_url` - The URL of the repository to analyze.
-/// * `start_commit` - The starting commit hash for the analysis.
-/// * `end_commit` - The ending commit hash for the analysis.
+/// * `range` - The commits to analyze.
+/// * `options` - Options selecting the optional sections to include in the analysis.
 ///
 /// # Returns
 ///
@@ -18,32 +37,133 @@ use crate::utils::RepositoryManager;
 ///
 /// # Errors
 ///
-/// Returns an error if there is an issue with repository access, cloning, or analysis.
+/// Returns an error if there is an issue with repository access, cloning, or analysis, a
+/// `RangeError` if the range is invalid, or an `OptionsError` if an option is.
 pub async fn analyze_commit_range_service(
-    repository: &Repository, start_commit: &str, end_commit: &str,
+    repository: &Repository, range: &RangeSpec, options: &CommitRangeOptions,
 ) -> Result<CommitRangeAnalysis, Box<dyn Error>> {
     let repo_manager = RepositoryManager::new(&repository.url(Protocol::Http).unwrap())?;
     let _repo = repo_manager.open_or_clone().await?;
     let local_path = repo_manager.get_local_path();
+    let cache_path = repo_manager.get_cache_path();
+
+    let (repository, range, options) = (repository.clone(), range.clone(), options.clone());
+    run_blocking(move || {
+        let repo = git2::Repository::open(&local_path)?;
+        let cache = CommitCache::open(&cache_path)?;
+        analyze_commit_range(&repo, cache, &repository, &range, &options)
+    })
+    .await
+}
+
+/// Runs the analyzers of the requested sections over a single walk of the range.
+fn analyze_commit_range(
+    repo: &git2::Repository, cache: CommitCache, repository: &Repository, range: &RangeSpec,
+    options: &CommitRangeOptions,
+) -> Result<CommitRangeAnalysis, Box<dyn Error + Send + Sync>> {
+    let timezone: Tz = options
+        .timezone
+        .parse()
+        .map_err(|_| OptionsError::UnknownTimezone(options.timezone.clone()))?;
+    let paths = PathFilter::new(&options.include_paths, &options.exclude_paths)
+        .map_err(OptionsError::InvalidPathGlob)?
+        .with_generated_excluded(options.exclude_generated);
+    let test_classifier = options
+        .includes(AnalysisSection::TestRatio)
+        .then(|| TestClassifier::new(&options.test_patterns))
+        .transpose()
+        .map_err(OptionsError::InvalidTestGlob)?;
+
+    let bot_detector = BotDetector::default().with_accounts(options.bot_accounts.clone());
+    let filter = CommitFilter::new(options.history_mode)
+        .with_excluded_bots(options.exclude_bots.then(|| bot_detector.clone()))
+        .with_paths(paths);
+    let range = CommitRange::resolve(repo, range, filter.follows_first_parent())?;
+    let pipeline = Pipeline::default()
+        .with_filter(filter)
+        .with_aliases(options.aliases.clone())
+        .with_cache(cache);
+
+    let mut code_churn = CodeChurnAnalyzer::default();
+    let mut top_contributors = TopContributorsAnalyzer::default()
+        .with_co_author_credit(options.co_author_credit)
+        .with_bot_detector(bot_detector);
+    let mut hotspots = options
+        .includes(AnalysisSection::Hotspots)
+        .then(HotspotAnalyzer::default);
+    let mut bus_factor = options
+        .includes(AnalysisSection::BusFactor)
+        .then(|| BusFactorAnalyzer::new(options.bus_factor_depth));
+    let mut ownership = options
+        .includes(AnalysisSection::Ownership)
+        .then_some(OwnershipAnalyzer);
+    let mut rework = options
+        .includes(AnalysisSection::Rework)
+        .then(|| ReworkAnalyzer::new(options.rework_window_days));
+    let mut coupling = options
+        .includes(AnalysisSection::Coupling)
+        .then(|| CouplingAnalyzer::new(options.coupling_min_co_changes));
+    let mut activity = options
+        .includes(AnalysisSection::Activity)
+        .then(|| ActivityAnalyzer::new(options.granularity, timezone));
+    let mut work_patterns = options
+        .includes(AnalysisSection::WorkPatterns)
+        .then(|| WorkPatternAnalyzer::new(options.working_hours).with_teams(options.teams.clone()));
+    let mut languages = options
+        .includes(AnalysisSection::Languages)
+        .then(LanguageAnalyzer::default);
+    let mut compliance = options
+        .includes(AnalysisSection::Compliance)
+        .then(|| ComplianceAnalyzer::new(options.compliance_rules.clone()));
+    let mut test_ratio = test_classifier.map(TestRatioAnalyzer::new);
+    let mut outliers = options
+        .includes(AnalysisSection::Outliers)
+        .then(|| OutlierAnalyzer::new(options.outlier_threshold));
 
-    let code_churn_analyzer = CodeChurnAnalyzer;
-    let top_contributors_analyzer = TopContributorsAnalyzer;
+    let mut visitors: Vec<&mut dyn CommitVisitor> = vec![&mut code_churn, &mut top_contributors];
+    visitors.extend(hotspots.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(bus_factor.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(ownership.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(rework.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(coupling.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(activity.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(work_patterns.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(languages.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(compliance.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(test_ratio.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    visitors.extend(outliers.as_mut().map(|a| a as &mut dyn CommitVisitor));
+    let mut context = pipeline.run(repo, &range, &mut visitors)?;
 
-    let code_churn_results = code_churn_analyzer
-        .analyze(&local_path, &start_commit, &end_commit)
-        .await?;
-    let mut top_contributors_results = top_contributors_analyzer
-        .analyze(&local_path, &start_commit, &end_commit)
-        .await?;
+    let code_churn_results = code_churn.finish(&mut context)?;
+    let mut top_contributors_results = top_contributors.finish(&mut context)?;
+    options
+        .rank_contributors_by
+        .sort(&mut top_contributors_results);
 
-    top_contributors_results.sort_by(|a, b| b.commits.cmp(&a.commits));
+    let files = options.includes(AnalysisSection::Files).then(|| {
+        code_churn_results
+            .iter()
+            .flat_map(|churn| churn.files().iter().cloned())
+            .collect()
+    });
+    let hotspots = hotspots.map(|a| a.finish(&mut context)).transpose()?;
+    let bus_factor = bus_factor.map(|a| a.finish(&mut context)).transpose()?;
+    let ownership = ownership.map(|a| a.finish(&mut context)).transpose()?;
+    let rework = rework.map(|a| a.finish(&mut context)).transpose()?;
+    let coupling = coupling.map(|a| a.finish(&mut context)).transpose()?;
+    let activity = activity.map(|a| a.finish(&mut context)).transpose()?;
+    let work_patterns = work_patterns.map(|a| a.finish(&mut context)).transpose()?;
+    let languages = languages.map(|a| a.finish(&mut context)).transpose()?;
+    let compliance = compliance.map(|a| a.finish(&mut context)).transpose()?;
+    let test_ratio = test_ratio.map(|a| a.finish(&mut context)).transpose()?;
+    let outliers = outliers.map(|a| a.finish(&mut context)).transpose()?;
 
     // Combine the results from different analyzers
     Ok(CommitRangeAnalysis {
         repository: repository.clone(),
         commit_range: CommitRangeDetails {
-            start_commit: start_commit.to_string(),
-            end_commit: end_commit.to_string(),
+            start_commit: range.start_commit.clone().unwrap_or_default(),
+            end_commit: range.end_commit.clone(),
             total_commits: code_churn_results.len() as i32,
             total_additions: code_churn_results
                 .iter()
@@ -53,13 +173,76 @@ pub async fn analyze_commit_range_service(
                 .iter()
                 .map(|c| c.deletions() as i32)
                 .sum(),
-            top_contributors: top_contributors_results
-                .iter()
-                .map(|c| Contributor {
-                    username: c.username.clone(),
-                    commits: c.commits as i32,
-                })
-                .collect(),
+            top_contributors: top_contributors_results,
         },
+        files,
+        hotspots,
+        bus_factor,
+        ownership,
+        rework,
+        coupling,
+        activity,
+        work_patterns,
+        languages,
+        compliance,
+        test_ratio,
+        outliers,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::{Connection, GitHubRepository, RangeError};
+    use crate::utils::testing::TestRepo;
+
+    use super::*;
+
+    #[test]
+    fn test_analyze_commit_range_keeps_client_errors_typed() {
+        let test_repo = TestRepo::new();
+        test_repo.write("README.md", "hello\n");
+        test_repo.commit("initial");
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CommitCache::open(dir.path().join("commits.redb")).unwrap();
+        let repository = Repository::GitHub(GitHubRepository {
+            owner: "acme".to_string(),
+            name: "app".to_string(),
+            connection: Connection::Https,
+        });
+        let analyze = |range: RangeSpec, options: CommitRangeOptions| {
+            analyze_commit_range(&test_repo.repo, cache.clone(), &repository, &range, &options)
+                .err()
+        };
+        assert!(analyze(RangeSpec::default(), CommitRangeOptions::default()).is_none());
+
+        let option_error = |options| {
+            let error = analyze(RangeSpec::default(), options).unwrap();
+            error.downcast::<OptionsError>().map(|error| *error).ok()
+        };
+        let error = option_error(CommitRangeOptions {
+            timezone: "Mars/Olympus_Mons".to_string(),
+            ..CommitRangeOptions::default()
+        });
+        assert!(matches!(error, Some(OptionsError::UnknownTimezone(_))));
+        let error = option_error(CommitRangeOptions {
+            include_paths: vec!["src/{lib".to_string()],
+            ..CommitRangeOptions::default()
+        });
+        assert!(matches!(error, Some(OptionsError::InvalidPathGlob(_))));
+        let error = option_error(CommitRangeOptions {
+            sections: vec![AnalysisSection::TestRatio],
+            test_patterns: HashMap::from([("Go".to_string(), vec!["[".to_string()])]),
+            ..CommitRangeOptions::default()
+        });
+        assert!(matches!(error, Some(OptionsError::InvalidTestGlob(_))));
+
+        let range = RangeSpec {
+            end_commit: Some("mian".to_string()),
+            ..RangeSpec::default()
+        };
+        let error = analyze(range, CommitRangeOptions::default()).unwrap();
+        assert!(error.is::<RangeError>());
+    }
+}
@@ -0,0 +1,60 @@
+use std::error::Error;
+
+use crate::analyzers::{Analyzer, ChangelogAnalyzer, Pipeline};
+use crate::changelog;
+use crate::models::{ChangelogOptions, Protocol, RangeSpec, Repository};
+use crate::repository::{BotDetector, CommitFilter, CommitRange};
+use crate::utils::RepositoryManager;
+
+use super::run_blocking;
+
+/// Generates a changelog of the conventional commits between two revisions of a repository.
+///
+/// # Arguments
+///
+/// * `repository` - The repository to generate the changelog for.
+/// * `start_commit` - The revision the changes are listed from, exclusive, such as a tag.
+/// * `end_commit` - The revision the changes are listed up to, inclusive.
+/// * `options` - Options selecting the format and the commits to list.
+///
+/// # Returns
+///
+/// A `Result` containing the rendered changelog if successful, or an `Error` if an error
+/// occurred.
+///
+/// # Errors
+///
+/// Returns an error if there is an issue with repository access, cloning, or analysis, a
+/// `RangeError` if a revision does not exist, or a `ChangelogError` if the template is invalid.
+pub async fn generate_changelog_service(
+    repository: &Repository, start_commit: &str, end_commit: &str, options: &ChangelogOptions,
+) -> Result<String, Box<dyn Error>> {
+    let repo_manager = RepositoryManager::new(&repository.url(Protocol::Http).unwrap())?;
+    let _repo = repo_manager.open_or_clone().await?;
+    let local_path = repo_manager.get_local_path();
+
+    let bot_detector = BotDetector::default().with_accounts(options.bot_accounts.clone());
+    let filter = CommitFilter::new(options.history_mode)
+        .with_excluded_bots(options.exclude_bots.then_some(bot_detector));
+    let first_parent = filter.follows_first_parent();
+    let pipeline = Pipeline::default()
+        .with_filter(filter)
+        .with_aliases(options.aliases.clone());
+    let mut analyzer = ChangelogAnalyzer::default().with_links(repository.clone());
+
+    let spec = RangeSpec {
+        start_commit: Some(start_commit.to_string()),
+        end_commit: Some(end_commit.to_string()),
+        ..RangeSpec::default()
+    };
+    let mut changelog = run_blocking(move || {
+        let repo = git2::Repository::open(&local_path)?;
+        let range = CommitRange::resolve(&repo, &spec, first_parent)?;
+        let mut context = pipeline.run(&repo, &range, &mut [&mut analyzer])?;
+        analyzer.finish(&mut context)
+    })
+    .await?;
+    changelog.version = options.version.clone();
+
+    Ok(changelog::render(&changelog, options.format, options.template.as_deref())?)
+}
@@ -1,8 +1,29 @@
 pub use analyze_commit_range::*;
+pub use generate_changelog::*;
+pub use recommend_version::*;
+use std::error::Error;
+
 use nject::{injectable, provider};
 
 mod analyze_commit_range;
 mod azure_service;
+mod generate_changelog;
 mod git_service;
 mod github_service;
+mod recommend_version;
 mod vcs_service;
+
+/// Runs a synchronous analysis on tokio's blocking thread pool, so walking and diffing a long
+/// history does not hold up the async workers.
+///
+/// Analysis errors are `Send`, so they cross back to the async side with their type, and
+/// callers can still tell a client error from a failed analysis.
+async fn run_blocking<T, F>(analysis: F) -> Result<T, Box<dyn Error>>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, Box<dyn Error + Send + Sync>> + Send + 'static,
+{
+    tokio::task::spawn_blocking(analysis)
+        .await?
+        .map_err(|e| e as Box<dyn Error>)
+}
@@ -0,0 +1,40 @@
+use std::error::Error;
+
+use crate::models::{Protocol, ReleaseOptions, Repository, VersionRecommendation};
+use crate::release;
+use crate::utils::RepositoryManager;
+
+use super::run_blocking;
+
+/// Recommends the next version of a repository from the conventional commits since its latest
+/// release tag.
+///
+/// # Arguments
+///
+/// * `repository` - The repository to recommend the next version of.
+/// * `target` - The revision to be released, such as a branch or a commit.
+/// * `options` - Options selecting the pre-release, the release tags and the commits to consider.
+///
+/// # Returns
+///
+/// A `Result` containing the `VersionRecommendation` if successful, or an `Error` if an error
+/// occurred.
+///
+/// # Errors
+///
+/// Returns an error if there is an issue with repository access or cloning, or a
+/// `ReleaseError` if the target does not exist or the pre-release identifier is invalid.
+pub async fn recommend_version_service(
+    repository: &Repository, target: &str, options: &ReleaseOptions,
+) -> Result<VersionRecommendation, Box<dyn Error>> {
+    let repo_manager = RepositoryManager::new(&repository.url(Protocol::Http).unwrap())?;
+    let _repo = repo_manager.open_or_clone().await?;
+    let local_path = repo_manager.get_local_path();
+
+    let (target, options) = (target.to_string(), options.clone());
+    run_blocking(move || {
+        let repo = git2::Repository::open(&local_path)?;
+        release::recommend_version(&repo, &target, &options)
+    })
+    .await
+}
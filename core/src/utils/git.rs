@@ -114,4 +114,13 @@ impl RepositoryManager {
     pub fn get_local_path(&self) -> String {
         self.local_path.clone()
     }
+
+    /// Get the path of the commit cache, kept next to the local clone
+    ///
+    /// # Returns
+    ///
+    /// A string containing the path of the commit cache of the repository
+    pub fn get_cache_path(&self) -> String {
+        format!("{}.redb", self.local_path)
+    }
 }
@@ -1,3 +1,5 @@
 pub use git::*;
 
 mod git;
+#[cfg(test)]
+pub(crate) mod testing;
@@ -0,0 +1,94 @@
+use std::fs;
+
+use git2::{Oid, Repository, Signature, Time};
+use tempfile::TempDir;
+
+/// A throwaway Git repository used to exercise analyzers in tests.
+pub(crate) struct TestRepo {
+    dir: TempDir,
+    pub repo: Repository,
+}
+
+impl TestRepo {
+    /// Initializes an empty repository in a temporary directory.
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        Self { dir, repo }
+    }
+
+    /// Returns the path of the working directory.
+    pub fn path(&self) -> &str {
+        self.dir.path().to_str().unwrap()
+    }
+
+    /// Writes a file relative to the working directory, creating parent directories.
+    pub fn write(&self, path: &str, contents: impl AsRef<[u8]>) {
+        let path = self.dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    /// Renames a file relative to the working directory.
+    pub fn rename(&self, from: &str, to: &str) {
+        fs::rename(self.dir.path().join(from), self.dir.path().join(to)).unwrap();
+    }
+
+    /// Removes a file relative to the working directory.
+    pub fn remove(&self, path: &str) {
+        fs::remove_file(self.dir.path().join(path)).unwrap();
+    }
+
+    /// Commits the whole working directory on top of `HEAD`.
+    pub fn commit(&self, message: &str) -> Oid {
+        self.commit_as("Jane Doe", "jane@example.com", Time::new(1_700_000_000, 0), message)
+    }
+
+    /// Commits the whole working directory on top of `HEAD` as the given author.
+    pub fn commit_as(&self, name: &str, email: &str, time: Time, message: &str) -> Oid {
+        let mut index = self.repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree = self.repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new(name, email, &time).unwrap();
+        let parent = self
+            .repo
+            .head()
+            .ok()
+            .map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        self.repo
+            .commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// Moves `HEAD` and the working directory to the given commit, discarding later commits.
+    pub fn reset(&self, id: Oid) {
+        let commit = self.repo.find_object(id, None).unwrap();
+        self.repo
+            .reset(&commit, git2::ResetType::Hard, None)
+            .unwrap();
+    }
+
+    /// Merges the given commit into `HEAD` with a merge commit, which becomes the new `HEAD`.
+    pub fn merge(&self, other: Oid, message: &str) -> Oid {
+        let head = self.repo.head().unwrap().peel_to_commit().unwrap();
+        let other = self.repo.find_commit(other).unwrap();
+        let mut index = self.repo.merge_commits(&head, &other, None).unwrap();
+        let tree = self
+            .repo
+            .find_tree(index.write_tree_to(&self.repo).unwrap())
+            .unwrap();
+        let signature =
+            Signature::new("Jane Doe", "jane@example.com", &Time::new(1_700_000_000, 0)).unwrap();
+        let id = self
+            .repo
+            .commit(Some("HEAD"), &signature, &signature, message, &tree, &[&head, &other])
+            .unwrap();
+        self.reset(id);
+        id
+    }
+}